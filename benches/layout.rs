@@ -0,0 +1,74 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use term_data_table::{Row, Table};
+
+/// Text with lots of places to break, like a stack trace.
+fn stack_trace(len: usize) -> String {
+    let mut out = String::new();
+    let mut frame = 0;
+    while out.len() < len {
+        out.push_str(&format!(
+            "  {frame}: my_crate::module::function_{frame}::{{{{closure}}}}\n             at ./src/module.rs:{frame}:5\n"
+        ));
+        frame += 1;
+    }
+    out
+}
+
+/// Text with few places to break, like a minified JSON blob.
+fn json_blob(len: usize) -> String {
+    let mut out = String::from("[");
+    let mut idx = 0;
+    while out.len() < len {
+        out.push_str(&format!("{{\"id\":{idx},\"name\":\"item-{idx}\"}},"));
+        idx += 1;
+    }
+    out.push(']');
+    out
+}
+
+/// A single token with no places to break, like a hex encoded hash, which has to be split into
+/// lines wherever it reaches the edge of the cell.
+fn hex_token(len: usize) -> String {
+    (0..len)
+        .map(|idx| char::from_digit((idx % 16) as u32, 16).unwrap())
+        .collect()
+}
+
+fn long_cells(c: &mut Criterion) {
+    let mut group = c.benchmark_group("long_cells");
+    for len in [1_000, 10_000, 100_000] {
+        let inputs = [
+            ("stack_trace", stack_trace(len)),
+            ("json", json_blob(len)),
+            ("hex_token", hex_token(len)),
+        ];
+        for (name, content) in inputs {
+            let table = Table::new().with_row(Row::new().with_cell("error").with_cell(content));
+            group.bench_with_input(BenchmarkId::new(name, len), &table, |b, table| {
+                b.iter(|| black_box(table.compute_layout(Some(80))))
+            });
+        }
+    }
+    group.finish();
+}
+
+fn many_rows(c: &mut Criterion) {
+    let table = Table::from_rows(
+        (0..10_000)
+            .map(|idx| {
+                Row::new()
+                    .with_cell(idx.to_string())
+                    .with_cell(format!(
+                        "row {idx} has some text that will need to be wrapped"
+                    ))
+                    .with_cell("short")
+            })
+            .collect(),
+    );
+    c.bench_function("many_rows", |b| {
+        b.iter(|| black_box(table.compute_layout(Some(60))))
+    });
+}
+
+criterion_group!(benches, long_cells, many_rows);
+criterion_main!(benches);
@@ -1,8 +1,10 @@
 use term_data_table::IntoRow;
 
 #[derive(Debug, IntoRow)]
+#[table(rename_all = "Title Case")]
 struct MyData<T: std::fmt::Display> {
     name: String,
+    #[table(align = "right")]
     age: u16,
     extra: T,
 }
@@ -20,5 +22,6 @@ impl MyData<String> {
 fn main() {
     let data = vec![MyData::new("John Doe", 28), MyData::new("Jane Foo", 72)];
 
-    println!("{}", term_data_table::data_table(&data))
+    println!("{}", term_data_table::data_table(&data));
+    println!("{}", term_data_table::record_table(&data[0]));
 }
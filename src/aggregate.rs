@@ -0,0 +1,194 @@
+use crate::{sort::parse_number, Alignment, Cell, Row, Table};
+use std::{fmt, sync::Arc};
+
+/// A function to combine the numbers in a column, used with `Aggregate::Custom`.
+pub type AggregateFn = dyn Fn(&[f64]) -> Option<f64> + Send + Sync;
+
+/// A way of combining the numbers in a column into a single value, e.g. for a footer row.
+#[derive(Clone)]
+pub enum Aggregate {
+    /// The sum of the numbers, or 0 if there are none.
+    Sum,
+    /// The mean of the numbers.
+    Mean,
+    /// The smallest number.
+    Min,
+    /// The largest number.
+    Max,
+    /// How many numbers there are.
+    Count,
+    /// Combine the numbers using a custom function.
+    Custom(Arc<AggregateFn>),
+}
+
+impl Aggregate {
+    /// Combine the numbers using a custom function.
+    pub fn custom(aggregate: impl Fn(&[f64]) -> Option<f64> + Send + Sync + 'static) -> Self {
+        Aggregate::Custom(Arc::new(aggregate))
+    }
+
+    /// Combine the given numbers.
+    ///
+    /// Returns `None` if there is no result, e.g. the mean of no numbers.
+    pub fn apply(&self, values: &[f64]) -> Option<f64> {
+        match self {
+            Aggregate::Sum => Some(values.iter().sum()),
+            Aggregate::Mean => {
+                (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+            }
+            Aggregate::Min => values.iter().copied().reduce(f64::min),
+            Aggregate::Max => values.iter().copied().reduce(f64::max),
+            Aggregate::Count => Some(values.len() as f64),
+            Aggregate::Custom(aggregate) => aggregate(values),
+        }
+    }
+}
+
+impl fmt::Debug for Aggregate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Aggregate::Sum => f.write_str("Sum"),
+            Aggregate::Mean => f.write_str("Mean"),
+            Aggregate::Min => f.write_str("Min"),
+            Aggregate::Max => f.write_str("Max"),
+            Aggregate::Count => f.write_str("Count"),
+            Aggregate::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+impl<'data> Table<'data> {
+    /// The numbers in the given column, not including the header or footer.
+    ///
+    /// Text is parsed the same way as `Comparator::Numeric`, and cells that aren't numbers are
+    /// skipped. A cell spanning several columns is used for each of them.
+    pub fn column_numbers(&self, column: usize) -> Vec<f64> {
+        self.rows
+            .iter()
+            .filter_map(|row| row.cell_at_column(column))
+            .filter_map(|cell| parse_number(cell.content_for_layout()))
+            .collect()
+    }
+
+    /// Combine the numbers in the given column, not including the header or footer.
+    pub fn aggregate(&self, column: usize, aggregate: &Aggregate) -> Option<f64> {
+        aggregate.apply(&self.column_numbers(column))
+    }
+
+    /// Build a row containing the given aggregates, for use as a footer.
+    ///
+    /// Each aggregate is right aligned in its column, and written with up to 6 decimal places.
+    /// `label` is put in the first column, unless it has an aggregate, and all other cells are
+    /// empty.
+    ///
+    ///# Example
+    ///
+    ///```
+    /// # use term_data_table::{Aggregate, Row, Table};
+    /// let mut table = Table::new()
+    ///     .with_row(Row::new().with_cell("apples").with_cell("3"))
+    ///     .with_row(Row::new().with_cell("pears").with_cell("4"));
+    /// table.set_footer(table.aggregate_row("Total", &[(1, Aggregate::Sum)]));
+    ///```
+    pub fn aggregate_row(&self, label: &str, aggregates: &[(usize, Aggregate)]) -> Row<'static> {
+        let mut row = Row::new();
+        for column in 0..self.num_columns() {
+            let cell = match aggregates.iter().find(|(col, _)| *col == column) {
+                Some((_, aggregate)) => {
+                    let text = self
+                        .aggregate(column, aggregate)
+                        .map(format_number)
+                        .unwrap_or_default();
+                    Cell::from(text).with_alignment(Alignment::Right)
+                }
+                None if column == 0 => Cell::from(label.to_owned()),
+                None => Cell::from(""),
+            };
+            row.add_cell(cell);
+        }
+        row
+    }
+}
+
+/// Write a number with up to 6 decimal places, without trailing zeros.
+fn format_number(value: f64) -> String {
+    let text = format!("{value:.6}");
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    match text {
+        "-0" => "0".into(),
+        text => text.into(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Aggregate;
+    use crate::{Row, Table, TableStyle};
+    use pretty_assertions::assert_eq;
+
+    fn sales() -> Table<'static> {
+        Table::new()
+            .with_style(TableStyle::SIMPLE)
+            .with_separate_rows(false)
+            .with_header(Row::new().with_cell("Item").with_cell("Price"))
+            .with_row(Row::new().with_cell("Rope").with_cell("1.1"))
+            .with_row(Row::new().with_cell("Nuts").with_cell("2.2"))
+            .with_row(Row::new().with_cell("Anvil").with_cell("1,500"))
+            .with_row(Row::new().with_cell("Gift").with_cell("free"))
+    }
+
+    #[test]
+    fn aggregates() {
+        let table = sales();
+        assert_eq!(table.column_numbers(1), vec![1.1, 2.2, 1500.0]);
+        assert_eq!(table.aggregate(1, &Aggregate::Count), Some(3.0));
+        assert_eq!(table.aggregate(1, &Aggregate::Min), Some(1.1));
+        assert_eq!(table.aggregate(1, &Aggregate::Max), Some(1500.0));
+        assert_eq!(table.aggregate(0, &Aggregate::Sum), Some(0.0));
+        assert_eq!(table.aggregate(0, &Aggregate::Mean), None);
+        let median = Aggregate::custom(|values| {
+            let mut values = values.to_vec();
+            values.sort_by(f64::total_cmp);
+            values.get(values.len() / 2).copied()
+        });
+        assert_eq!(table.aggregate(1, &median), Some(2.2));
+    }
+
+    #[test]
+    fn footer() {
+        let mut table = sales();
+        table.set_footer(table.aggregate_row("Total", &[(1, Aggregate::Sum)]));
+        table.sort_by_column(0, Default::default());
+        table.filter(|row| row.cells()[1].content() != "free");
+        let expected = "+-------+--------+
+| Item  | Price  |
++-------+--------+
+| Anvil | 1,500  |
+| Nuts  | 2.2    |
+| Rope  | 1.1    |
++-------+--------+
+| Total | 1503.3 |
++-------+--------+
+";
+        assert_eq!(expected, table.to_string());
+
+        table.set_footer_style(TableStyle {
+            horizontal: '=',
+            ..TableStyle::SIMPLE
+        });
+        let expected = r"\begin{tabular}{|l|l|}
+\hline
+Item & Price \\
+\hline
+Anvil & 1,500 \\
+Nuts & 2.2 \\
+Rope & 1.1 \\
+\hline
+Total & \multicolumn{1}{r|}{1503.3} \\
+\hline
+\end{tabular}
+";
+        assert_eq!(expected, table.to_latex().to_string());
+        assert!(table.to_string().contains("+=======+========+\n| Total |"));
+    }
+}
@@ -0,0 +1,142 @@
+use crate::{Alignment, Layout, Rendered, Renderer, Row, Table};
+use std::fmt;
+
+/// Formats a table as an AsciiDoc table (delimited by `|===`).
+///
+/// Column alignments are written in the `cols` attribute,
+/// and cells that span multiple columns or are aligned differently to the rest of their column
+/// get a cell specifier (e.g. `2+^|`).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AsciiDoc;
+
+impl AsciiDoc {
+    fn render_row(
+        &self,
+        row: &Row,
+        alignments: &[Alignment],
+        f: &mut dyn fmt::Write,
+    ) -> fmt::Result {
+        let mut idx = 0;
+        for cell in row.cells.iter() {
+            if idx > 0 {
+                f.write_char(' ')?;
+            }
+            if cell.col_span > 1 {
+                write!(f, "{}+", cell.col_span)?;
+            }
+            if alignments.get(idx).copied().unwrap_or(Alignment::Left) != cell.alignment {
+                f.write_char(alignment_spec(cell.alignment))?;
+            }
+            f.write_char('|')?;
+            write_escaped(cell.content_for_layout(), f)?;
+            idx += cell.col_span;
+        }
+        // Fill in any columns this row is missing.
+        for col in idx..alignments.len() {
+            f.write_str(if col > 0 { " |" } else { "|" })?;
+        }
+        writeln!(f)
+    }
+}
+
+impl Renderer for AsciiDoc {
+    fn render(&self, table: &Table, _: &Layout, f: &mut dyn fmt::Write) -> fmt::Result {
+        let alignments = table.column_alignments();
+
+        f.write_str("[cols=\"")?;
+        for (idx, alignment) in alignments.iter().enumerate() {
+            if idx > 0 {
+                f.write_char(',')?;
+            }
+            f.write_char(alignment_spec(*alignment))?;
+        }
+        f.write_char('"')?;
+        match (&table.header, &table.footer) {
+            (Some(_), Some(_)) => f.write_str(",options=\"header,footer\"")?,
+            (Some(_), None) => f.write_str(",options=\"header\"")?,
+            (None, Some(_)) => f.write_str(",options=\"footer\"")?,
+            (None, None) => (),
+        }
+        writeln!(f, "]")?;
+        writeln!(f, "|===")?;
+        if let Some(header) = &table.header {
+            self.render_row(header, &alignments, f)?;
+            writeln!(f)?;
+        }
+        for row in table.rows.iter() {
+            self.render_row(row, &alignments, f)?;
+        }
+        if let Some(footer) = &table.footer {
+            writeln!(f)?;
+            self.render_row(footer, &alignments, f)?;
+        }
+        writeln!(f, "|===")
+    }
+}
+
+impl<'data> Table<'data> {
+    /// Format the table as an AsciiDoc table.
+    ///
+    /// Any ANSI escape sequences in cells are removed.
+    pub fn to_asciidoc(&self) -> Rendered<'_, AsciiDoc> {
+        self.render_with(AsciiDoc)
+    }
+}
+
+fn alignment_spec(alignment: Alignment) -> char {
+    match alignment {
+        Alignment::Left => '<',
+        Alignment::Center => '^',
+        Alignment::Right => '>',
+    }
+}
+
+/// Write out text, escaping the cell separator.
+fn write_escaped(text: &str, f: &mut dyn fmt::Write) -> fmt::Result {
+    for ch in text.chars() {
+        if ch == '|' {
+            f.write_char('\\')?;
+        }
+        f.write_char(ch)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Alignment, Cell, Row, Table};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn asciidoc() {
+        let table = Table::new()
+            .with_header(
+                Row::new()
+                    .with_cell("Name")
+                    .with_cell(Cell::from("Cost").with_alignment(Alignment::Right)),
+            )
+            .with_row(
+                Row::new()
+                    .with_cell("Fish | chips")
+                    .with_cell(Cell::from("$5").with_alignment(Alignment::Right)),
+            )
+            .with_row(
+                Row::new().with_cell(
+                    Cell::from("Total")
+                        .with_col_span(2)
+                        .with_alignment(Alignment::Center),
+                ),
+            )
+            .with_row(Row::new().with_cell("Short row"));
+        let expected = r#"[cols="<,>",options="header"]
+|===
+|Name |Cost
+
+|Fish \| chips |$5
+2+^|Total
+|Short row |
+|===
+"#;
+        assert_eq!(expected, table.to_asciidoc().to_string());
+    }
+}
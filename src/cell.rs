@@ -1,11 +1,14 @@
 use itertools::Itertools;
-use lazy_static;
 use regex::Regex;
-use std::{borrow::Cow, cell::RefCell, fmt, iter};
-use unicode_width::UnicodeWidthChar;
+use std::{
+    borrow::Cow,
+    fmt, iter,
+    ops::Range,
+    sync::atomic::{AtomicU64, Ordering},
+};
 
 use unicode_linebreak::{linebreaks, BreakOpportunity};
-use unicode_width::UnicodeWidthStr;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 /// Represents the horizontal alignment of content within a cell.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -27,12 +30,11 @@ pub struct Cell<'txt> {
     pub(crate) alignment: Alignment,
     pub(crate) pad_content: bool,
 
-    /// Positions we should split the text into multiple lines, if any.
-    ///
-    /// Is rebuild as needed.
-    layout_newlines: RefCell<Option<Vec<usize>>>,
-
     content_without_ansi_esc: Option<String>,
+    /// The width of the widest line, only breaking at newlines (excluding padding).
+    natural_width: usize,
+    /// Changes whenever something that affects layout changes. Used as a key in `LayoutCache`.
+    pub(crate) revision: u64,
 }
 
 impl<'txt> Default for Cell<'txt> {
@@ -43,8 +45,9 @@ impl<'txt> Default for Cell<'txt> {
             alignment: Alignment::Left,
             pad_content: true,
 
-            layout_newlines: RefCell::new(None),
             content_without_ansi_esc: None,
+            natural_width: 0,
+            revision: next_revision(),
         }
     }
 }
@@ -55,20 +58,25 @@ impl<'txt> Cell<'txt> {
             content: Cow::Owned(content),
             ..Default::default()
         };
-        this.update_without_ansi_esc();
+        this.update_content();
         this
     }
 
     /// Special builder that is slightly more efficient than using `From<String>`.
     fn borrowed(content: &'txt str) -> Self {
         let mut this = Self {
-            content: Cow::Borrowed(content.as_ref()),
+            content: Cow::Borrowed(content),
             ..Default::default()
         };
-        this.update_without_ansi_esc();
+        this.update_content();
         this
     }
 
+    /// The content of the cell, including any ANSI escape sequences.
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
     pub fn with_content(mut self, content: impl Into<Cow<'txt, str>>) -> Self {
         self.set_content(content);
         self
@@ -76,23 +84,31 @@ impl<'txt> Cell<'txt> {
 
     pub fn set_content(&mut self, content: impl Into<Cow<'txt, str>>) -> &mut Self {
         self.content = content.into();
-        self.update_without_ansi_esc();
+        self.update_content();
         self
     }
 
-    fn content_for_layout(&self) -> &str {
+    /// The content with any ANSI escape sequences removed.
+    pub(crate) fn content_for_layout(&self) -> &str {
         self.content_without_ansi_esc
-            .as_ref()
-            .map(|s| s.as_str())
+            .as_deref()
             .unwrap_or(&self.content)
     }
 
-    fn update_without_ansi_esc(&mut self) {
+    /// Recalculate anything that depends on the content.
+    fn update_content(&mut self) {
         self.content_without_ansi_esc = if ANSI_ESC_RE.is_match(&self.content) {
             Some(ANSI_ESC_RE.split(&self.content).collect())
         } else {
             None
         };
+        self.natural_width = natural_width(self.content_for_layout());
+        self.revision = next_revision();
+    }
+
+    /// The number of columns this cell spans.
+    pub fn col_span(&self) -> usize {
+        self.col_span
     }
 
     /// Set the number of columns this cell spans.
@@ -113,10 +129,14 @@ impl<'txt> Cell<'txt> {
     pub fn set_col_span(&mut self, col_span: usize) -> &mut Self {
         assert!(col_span > 0, "cannot have a col_span of 0");
         self.col_span = col_span;
-        *self.layout_newlines.borrow_mut() = None;
+        self.revision = next_revision();
         self
     }
 
+    pub fn alignment(&self) -> Alignment {
+        self.alignment
+    }
+
     pub fn with_alignment(mut self, alignment: Alignment) -> Self {
         self.set_alignment(alignment);
         self
@@ -124,10 +144,14 @@ impl<'txt> Cell<'txt> {
 
     pub fn set_alignment(&mut self, alignment: Alignment) -> &mut Self {
         self.alignment = alignment;
-        *self.layout_newlines.borrow_mut() = None;
         self
     }
 
+    /// Whether a space is added to either side of the content.
+    pub fn has_padding(&self) -> bool {
+        self.pad_content
+    }
+
     pub fn with_padding(mut self, padding: bool) -> Self {
         self.set_padding(padding);
         self
@@ -135,70 +159,82 @@ impl<'txt> Cell<'txt> {
 
     pub fn set_padding(&mut self, padding: bool) -> &mut Self {
         self.pad_content = padding;
-        *self.layout_newlines.borrow_mut() = None;
+        self.revision = next_revision();
         self
     }
 
-    /// Calculate positions of newlines.
+    /// Calculate where the text should be split into lines.
     ///
     /// Passed width includes padding spaces (if Some).
     ///
-    /// Returns the total number of lines to be drawn.
-    // The meaining of the parameter option None (means unbounded) is different from layout_width =
-    // None (means cache is stale)
-    pub(crate) fn layout(&self, width: Option<usize>) -> usize {
+    /// Returns the position of each line in `content_for_layout`, without trailing whitespace.
+    // The meaining of the parameter option None (means unbounded)
+    pub(crate) fn layout(&self, width: Option<usize>) -> Vec<Range<usize>> {
         // We can just pretend we have loads of space - we only calculate linebreaks here.
         let width = width.unwrap_or(usize::MAX);
-        if width < 1 || (self.pad_content && width < 3) {
-            panic!("cell too small to show anything (width = {width})");
-        }
         let content_width = if self.pad_content {
             width.saturating_sub(2)
         } else {
             width
         };
-        let mut ln = self.layout_newlines.borrow_mut();
-        let ln = ln.get_or_insert(vec![]);
-        ln.clear();
-        ln.push(0);
-
-        let mut s = self.content_for_layout();
-        // Go through potential linebreak locations to find where we should break.
-        let mut acc = 0;
-        while let Some(idx) = next_linebreak(s, content_width) {
-            s = &s[idx..];
-            ln.push(idx + acc);
-            acc += idx;
-        }
-        // the above method always ends the text with a newline, so pop it.
-        ln.pop();
-        // return number of lines
-        ln.len()
-    }
 
-    /// The minium width required to display the cell correctly.
-    ///
-    /// If `only_mandatory` is passed, then only mandatory newlines will be considered, meaning the
-    /// width will be larger.
-    pub(crate) fn min_width(&self, only_mandatory: bool) -> usize {
         let content = self.content_for_layout();
-        let max_newline_gap = linebreaks(content).filter_map(|(idx, ty)| {
-            if only_mandatory && !matches!(ty, BreakOpportunity::Mandatory) {
-                None
-            } else {
-                Some(idx)
+        // Empty cells are laid out with no space for content.
+        if content_width == 0 && self.natural_width > 0 {
+            panic!("cell too small to show anything (width = {width})");
+        }
+        let mut lines = vec![];
+        // The start of the current line.
+        let mut start = 0;
+        // How far we have got through the content. If this is after `start`, it is a break
+        // opportunity we can use.
+        let mut last = 0;
+        // The width of `content[start..last]`.
+        let mut line_width = 0;
+        // Each piece of text between break opportunities is measured once, so this is linear in the
+        // length of the content.
+        for (idx, ty) in linebreaks(content) {
+            let segment = &content[last..idx];
+            let mut segment_width = segment.width();
+            // Splitting a long word only takes text from the front, so the trailing whitespace
+            // stays the same and only needs measuring once.
+            let trailing_width = segment[segment.trim_end().len()..].width();
+            while line_width + segment_width > content_width {
+                if last > start {
+                    // first use the previous break opportunity if there is one
+                    lines.push(trimmed_line(content, start, last));
+                    start = last;
+                    line_width = 0;
+                } else if segment_width.saturating_sub(trailing_width) <= content_width {
+                    // trailing whitespace is not drawn, so doesn't need to fit. The line will end
+                    // here, as nothing else will fit after it.
+                    break;
+                } else {
+                    // next, break the word as late as possible, or after 1 char to be
+                    // deterministic (we have a very narrow cell)
+                    let (split, split_width) = split_word(&content[last..idx], content_width);
+                    lines.push(trimmed_line(content, last, last + split));
+                    last += split;
+                    start = last;
+                    segment_width -= split_width;
+                }
+            }
+            line_width += segment_width;
+            last = idx;
+            // we must insert a linebreak here, unless we already did when splitting a word.
+            if matches!(ty, BreakOpportunity::Mandatory) && last > start {
+                lines.push(trimmed_line(content, start, last));
+                start = last;
+                line_width = 0;
             }
-        });
-        let max_newline_gap = iter::once(0)
-            .chain(max_newline_gap)
-            .chain(iter::once(content.len()))
-            .tuple_windows()
-            .map(|(start, end)| content[start..end].width())
-            .max()
-            .unwrap_or(0);
+        }
+        lines
+    }
 
+    /// The minium width required to display the cell without wrapping, other than at newlines.
+    pub(crate) fn min_width(&self) -> usize {
         // We need space for the padding if the user specified to use it.
-        max_newline_gap + if self.pad_content { 2 } else { 0 }
+        self.natural_width + if self.pad_content { 2 } else { 0 }
     }
 
     /// Get the width of this cell, given the cell widths.
@@ -218,24 +254,15 @@ impl<'txt> Cell<'txt> {
 
     /// Write out the given line to the formatter.
     ///
-    /// You must call `layout` (which lays out the text)  before calling this method, otherwise
-    /// you may get panics or garbage.
+    /// The line is a range in `content_for_layout`, as returned by `layout`.
     pub(crate) fn render_line(
         &self,
-        line_idx: usize,
+        line: Range<usize>,
         width: usize,
-        f: &mut fmt::Formatter,
+        f: &mut dyn fmt::Write,
     ) -> fmt::Result {
-        let newlines = self.layout_newlines.borrow();
-        let newlines = newlines.as_ref().expect("missed call to `layout`");
-        let line = match newlines.get(line_idx) {
-            Some(&start_idx) => match newlines.get(line_idx + 1) {
-                Some(&end_idx) => &self.content[start_idx..end_idx],
-                None => &self.content[start_idx..],
-            },
-            // This will be the case if we already drew all the lines.
-            None => "",
-        };
+        let Range { start, end } = line;
+        let line = &self.content_for_layout()[start..end];
 
         let (front_pad, back_pad) = self.get_padding(width, line.width());
         let edge = self.edge_char();
@@ -243,13 +270,75 @@ impl<'txt> Cell<'txt> {
         for _ in 0..front_pad {
             f.write_str(" ")?;
         }
-        f.write_str(line)?;
+        if self.content_without_ansi_esc.is_some() && start < end {
+            self.write_with_ansi_esc(start, end, f)?;
+        } else {
+            f.write_str(line)?;
+        }
         for _ in 0..back_pad {
             f.write_str(" ")?;
         }
         f.write_str(edge)
     }
 
+    /// Write out the content between the given positions in `content_for_layout`, including any
+    /// ANSI escape sequences.
+    ///
+    /// Escape sequences from earlier lines are repeated at the start, and styles are reset at the
+    /// end, so that styles are not lost when wrapping and don't leak into the table borders.
+    fn write_with_ansi_esc(&self, start: usize, end: usize, f: &mut dyn fmt::Write) -> fmt::Result {
+        // escape sequences in effect before this line.
+        let mut active: Vec<&str> = vec![];
+        // whether we have written anything (and so `active` has been written).
+        let mut started = false;
+        // whether we have written any escape sequences that need resetting.
+        let mut styled = false;
+        // position in `content_for_layout`.
+        let mut pos = 0;
+        // position in `content`.
+        let mut last = 0;
+        let escapes = ANSI_ESC_RE
+            .find_iter(&self.content)
+            .map(|esc| (esc.start(), esc.as_str()))
+            .chain(std::iter::once((self.content.len(), "")));
+        for (esc_start, esc) in escapes {
+            let text = &self.content[last..esc_start];
+            let (from, to) = (start.max(pos), end.min(pos + text.len()));
+            if from < to {
+                if !started {
+                    active.iter().try_for_each(|esc| f.write_str(esc))?;
+                    started = true;
+                    styled = !active.is_empty();
+                }
+                f.write_str(&text[from - pos..to - pos])?;
+            }
+            pos += text.len();
+            last = esc_start + esc.len();
+
+            if pos < start {
+                if esc == "\x1b[0m" || esc == "\x1b[m" {
+                    active.clear();
+                } else {
+                    active.push(esc);
+                }
+            } else if pos < end {
+                if !started {
+                    active.iter().try_for_each(|esc| f.write_str(esc))?;
+                    started = true;
+                    styled = !active.is_empty();
+                }
+                f.write_str(esc)?;
+                styled |= !esc.is_empty();
+            } else {
+                break;
+            }
+        }
+        if styled {
+            f.write_str("\x1b[0m")?;
+        }
+        Ok(())
+    }
+
     /// Returns the number of spaces that should be placed before and after the text (excluding the
     /// single padding char)
     ///
@@ -294,47 +383,51 @@ impl<'txt> From<&'txt str> for Cell<'txt> {
 // Will match any ansi escape sequence.
 // Taken from https://github.com/mitsuhiko/console
 lazy_static! {
-    static ref ANSI_ESC_RE: Regex =
+    pub(crate) static ref ANSI_ESC_RE: Regex =
         Regex::new(r"[\x1b\x9b][\[()#;?]*(?:[0-9]{1,4}(?:;[0-9]{0,4})*)?[0-9A-PRZcf-nqry=><]")
             .unwrap();
 }
 
-/// Find where to put the next linebreak, or return None if we don't need any more.
-fn next_linebreak(text: &str, max_width: usize) -> Option<usize> {
-    let mut prev = None;
-    for (idx, ty) in linebreaks(text) {
-        if text[..idx].width() > max_width {
-            // first use the previous linebreak if there is one
-            if let Some(prev) = prev {
-                return Some(prev);
-            };
-            // next, find a character break
-            if let Some(linebreak) = next_linebreak_midword(text, max_width) {
-                return Some(linebreak);
-            }
-            // finally, do 1 char per line to be deterministic (we have a very narrow cell)
-            return text.chars().next().map(|ch| ch.width()).flatten();
-        } else if matches!(ty, BreakOpportunity::Mandatory) {
-            // we must insert a linebreak here
-            return Some(idx);
-        } else {
-            prev = Some(idx);
-        }
-    }
-    None
+fn next_revision() -> u64 {
+    static NEXT_REVISION: AtomicU64 = AtomicU64::new(0);
+    NEXT_REVISION.fetch_add(1, Ordering::Relaxed)
 }
 
-// TODO use midpoint-based search
-fn next_linebreak_midword(text: &str, max_width: usize) -> Option<usize> {
-    let mut prev = None;
-    for (idx, _) in text.char_indices() {
-        if text[..idx].width() > max_width {
-            return prev;
-        } else {
-            prev = Some(idx);
+/// The width of the widest line in the text, only breaking at mandatory linebreaks (newlines).
+fn natural_width(content: &str) -> usize {
+    let newlines = linebreaks(content)
+        .filter(|(_, ty)| matches!(ty, BreakOpportunity::Mandatory))
+        .map(|(idx, _)| idx);
+    iter::once(0)
+        .chain(newlines)
+        .chain(iter::once(content.len()))
+        .tuple_windows()
+        .map(|(start, end)| content[start..end].width())
+        .max()
+        .unwrap_or(0)
+}
+
+/// The range of `content[start..end]`, without trailing whitespace.
+///
+/// Lines end at a break opportunity, which may be whitespace or a newline.
+fn trimmed_line(content: &str, start: usize, end: usize) -> Range<usize> {
+    start..start + content[start..end].trim_end().len()
+}
+
+/// Find where to break a word that is too wide to fit on a line.
+///
+/// Returns the position to break at and the width of the text before it. Breaks as late as
+/// possible, but always after at least 1 char. Whitespace at the end of a line is not drawn, so it
+/// is kept on the line even if it doesn't fit.
+fn split_word(word: &str, max_width: usize) -> (usize, usize) {
+    let (mut split, mut width) = (0, 0);
+    for (idx, ch) in word.char_indices() {
+        let ch_width = ch.width().unwrap_or(0);
+        if split > 0 && width + ch_width > max_width && !ch.is_whitespace() {
+            break;
         }
+        split = idx + ch.len_utf8();
+        width += ch_width;
     }
-    // we should not reach here, because we already found a potential breakpoint that was too big
-    // for the line.
-    unreachable!()
+    (split, width)
 }
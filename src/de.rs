@@ -0,0 +1,315 @@
+use crate::{sort::strip_number_separators, Cell, Row, Table};
+use anyhow::anyhow;
+use serde::de::{self, DeserializeOwned, IntoDeserializer, Visitor};
+use std::fmt;
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[derive(Debug)]
+struct Error(String);
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl de::Error for Error {
+    fn custom<T>(msg: T) -> Self
+    where
+        T: fmt::Display,
+    {
+        Error(msg.to_string())
+    }
+}
+
+impl<'data> Table<'data> {
+    /// Read each row back into a record, using the header for field names.
+    ///
+    /// Rows are read like maps from header text to cell text, or like sequences if there is no
+    /// header. Cell text is parsed as whatever type each field needs: numbers can have `,` or `_`
+    /// separators, empty cells are `None`, and nested values are read as JSON. This is the
+    /// reverse of `Table::from_serde`, and can be used with `Table::parse` to read tables that
+    /// have been written out as text.
+    pub fn deserialize_rows<T: DeserializeOwned>(&self) -> anyhow::Result<Vec<T>> {
+        let header: Option<Vec<&str>> = self.header.as_ref().map(|header| {
+            (0..self.num_columns())
+                .map(|col| {
+                    header
+                        .cell_at_column(col)
+                        .map_or("", Cell::content_for_layout)
+                })
+                .collect()
+        });
+        self.rows
+            .iter()
+            .enumerate()
+            .map(|(idx, row)| {
+                T::deserialize(RowDeserializer {
+                    row,
+                    header: header.as_deref(),
+                })
+                .map_err(|e| anyhow!("row {}: {e}", idx + 1))
+            })
+            .collect()
+    }
+}
+
+struct RowDeserializer<'a> {
+    row: &'a Row<'a>,
+    header: Option<&'a [&'a str]>,
+}
+
+impl<'de> de::Deserializer<'de> for RowDeserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.header {
+            Some(header) => {
+                let fields = header.iter().enumerate().filter_map(|(col, key)| {
+                    let cell = self.row.cell_at_column(col)?;
+                    Some((*key, CellDeserializer(cell.content_for_layout())))
+                });
+                visitor.visit_map(de::value::MapDeserializer::new(fields))
+            }
+            None => {
+                let cells = self.row.cells.iter();
+                let cells = cells.map(|cell| CellDeserializer(cell.content_for_layout()));
+                visitor.visit_seq(de::value::SeqDeserializer::new(cells))
+            }
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        option unit unit_struct newtype_struct seq tuple tuple_struct map struct enum identifier
+        ignored_any
+    }
+}
+
+/// Reads the text of a cell as whatever type is asked for.
+#[derive(Clone, Copy)]
+struct CellDeserializer<'a>(&'a str);
+
+impl<'de> IntoDeserializer<'de, Error> for CellDeserializer<'de> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+impl<'de> CellDeserializer<'de> {
+    fn parse<T: std::str::FromStr>(self, expected: &str) -> Result<T> {
+        strip_number_separators(self.0)
+            .parse()
+            .map_err(|_| Error(format!("expected {expected}, found `{}`", self.0)))
+    }
+
+    fn json<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let mut json = serde_json::Deserializer::from_str(self.0);
+        de::Deserializer::deserialize_any(&mut json, visitor).map_err(de::Error::custom)
+    }
+}
+
+impl<'de> de::Deserializer<'de> for CellDeserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_borrowed_str(self.0)
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.0.trim() {
+            "true" => visitor.visit_bool(true),
+            "false" => visitor.visit_bool(false),
+            _ => Err(Error(format!("expected a bool, found `{}`", self.0))),
+        }
+    }
+
+    fn deserialize_i8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_i8(self.parse("an integer")?)
+    }
+
+    fn deserialize_i16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_i16(self.parse("an integer")?)
+    }
+
+    fn deserialize_i32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_i32(self.parse("an integer")?)
+    }
+
+    fn deserialize_i64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_i64(self.parse("an integer")?)
+    }
+
+    fn deserialize_u8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_u8(self.parse("an integer")?)
+    }
+
+    fn deserialize_u16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_u16(self.parse("an integer")?)
+    }
+
+    fn deserialize_u32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_u32(self.parse("an integer")?)
+    }
+
+    fn deserialize_u64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_u64(self.parse("an integer")?)
+    }
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_f32(self.parse("a number")?)
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_f64(self.parse("a number")?)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        if self.0.is_empty() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.json(visitor)
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value> {
+        self.json(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.json(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.json(visitor)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.json(visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        // Only unit variants can be read, as they are written as just their name.
+        visitor.visit_enum(self.0.into_deserializer())
+    }
+
+    serde::forward_to_deserialize_any! {
+        i128 u128 char str string bytes byte_buf identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Table, TableParser, TableStyle};
+    use pretty_assertions::assert_eq;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum Kind {
+        Cat,
+        Dog,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Pet {
+        name: String,
+        kind: Kind,
+        age: Option<u32>,
+        weight: f64,
+        toys: Vec<String>,
+    }
+
+    #[test]
+    fn round_trip() {
+        let pets = vec![
+            Pet {
+                name: "Rex the very good dog".into(),
+                kind: Kind::Dog,
+                age: Some(1200),
+                weight: 30.5,
+                toys: vec!["ball".into(), "stick".into()],
+            },
+            Pet {
+                name: "Tom".into(),
+                kind: Kind::Cat,
+                age: None,
+                weight: 4.0,
+                toys: vec![],
+            },
+        ];
+        let options = crate::SerdeOptions::new().with_thousands_separator(',');
+        for style in [TableStyle::SIMPLE, TableStyle::THIN, TableStyle::BLANK] {
+            let table = Table::from_serde_with(&pets, &options)
+                .unwrap()
+                .with_style(style);
+            let text = table.fixed_width(50).to_string();
+            let parsed = Table::parse(&text).unwrap();
+            assert_eq!(parsed.deserialize_rows::<Pet>().unwrap(), pets);
+        }
+
+        let table = Table::from_serde(&pets).unwrap().with_separate_rows(false);
+        let parsed = TableParser::new()
+            .with_separate_rows(false)
+            .parse(&table.to_string())
+            .unwrap();
+        assert_eq!(parsed.deserialize_rows::<Pet>().unwrap(), pets);
+
+        let lines = TableParser::new().with_separate_rows(false);
+        let tuples: Vec<(String, u8)> = lines
+            .clone()
+            .with_header(false)
+            .parse("| a | 1 |\n| b | 2 |")
+            .unwrap()
+            .deserialize_rows()
+            .unwrap();
+        assert_eq!(tuples, vec![("a".into(), 1), ("b".into(), 2)]);
+
+        let table = lines.parse("| name | age |\n| Rex  | old |").unwrap();
+        let err = table.deserialize_rows::<Pet>().unwrap_err();
+        assert_eq!(err.to_string(), "row 1: expected an integer, found `old`");
+    }
+}
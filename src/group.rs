@@ -0,0 +1,139 @@
+use crate::{Row, Table};
+use std::collections::HashMap;
+
+impl<'data> Table<'data> {
+    /// Group rows with the same text in the given column together, and put a header row before
+    /// each group.
+    ///
+    /// Groups are in the order their first row appears, and rows stay in the same order within
+    /// each group. The group header is a copy of the cell in the given column, spanning all
+    /// columns. Rows without a cell in the column are grouped together under an empty header.
+    ///
+    /// Group headers and subtotals are always separated from the rows around them. Rows in the
+    /// same group are separated or not depending on `has_separate_rows`.
+    pub fn group_by(&mut self, column: usize) -> &mut Self {
+        self.group_rows(column, |_| None)
+    }
+
+    /// Like `group_by`, but also add a subtotal row after each group.
+    ///
+    /// `subtotal` is called with the rows in each group, and returns the subtotal row.
+    pub fn group_by_with_subtotals(
+        &mut self,
+        column: usize,
+        mut subtotal: impl FnMut(&[Row<'data>]) -> Row<'data>,
+    ) -> &mut Self {
+        self.group_rows(column, |rows| Some(subtotal(rows)))
+    }
+
+    fn group_rows(
+        &mut self,
+        column: usize,
+        mut subtotal: impl FnMut(&[Row<'data>]) -> Option<Row<'data>>,
+    ) -> &mut Self {
+        let num_columns = self.num_columns().max(1);
+        // Groups in order of first appearance.
+        let mut groups: Vec<Vec<Row<'data>>> = vec![];
+        let mut group_idxs: HashMap<String, usize> = HashMap::new();
+        for row in std::mem::take(&mut self.rows) {
+            let key = row
+                .cell_at_column(column)
+                .map(|cell| cell.content_for_layout().to_owned())
+                .unwrap_or_default();
+            let idx = *group_idxs.entry(key).or_insert_with(|| {
+                groups.push(vec![]);
+                groups.len() - 1
+            });
+            groups[idx].push(row);
+        }
+
+        for group in groups {
+            let header_cell = group[0]
+                .cell_at_column(column)
+                .cloned()
+                .unwrap_or_else(|| "".into())
+                .with_col_span(num_columns);
+            let mut header = Row::new().with_cell(header_cell);
+            header.always_separated = true;
+            self.rows.push(header);
+            let subtotal = subtotal(&group);
+            self.rows.extend(group);
+            if let Some(mut subtotal) = subtotal {
+                subtotal.always_separated = true;
+                self.rows.push(subtotal);
+            }
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Alignment, Cell, Row, Table, TableStyle};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn group_by() {
+        let mut table = Table::new()
+            .with_style(TableStyle::SIMPLE)
+            .with_separate_rows(false)
+            .with_header(
+                Row::new()
+                    .with_cell("Customer")
+                    .with_cell("Item")
+                    .with_cell("Cost"),
+            )
+            .with_row(
+                Row::new()
+                    .with_cell("Acme")
+                    .with_cell("Rope")
+                    .with_cell("10"),
+            )
+            .with_row(
+                Row::new()
+                    .with_cell("Bolt")
+                    .with_cell("Nuts")
+                    .with_cell("2"),
+            )
+            .with_row(
+                Row::new()
+                    .with_cell("Acme")
+                    .with_cell("Anvil")
+                    .with_cell("150"),
+            );
+        table.group_by_with_subtotals(0, |rows| {
+            let total: u32 = rows
+                .iter()
+                .map(|row| row.cells()[2].content().parse::<u32>().unwrap())
+                .sum();
+            Row::new()
+                .with_cell(Cell::from("Subtotal").with_col_span(2))
+                .with_cell(Cell::from(total.to_string()).with_alignment(Alignment::Right))
+        });
+        let expected = "+----------+-------+------+
+| Customer | Item  | Cost |
++----------+-------+------+
+| Acme                    |
++----------+-------+------+
+| Acme     | Rope  | 10   |
+| Acme     | Anvil | 150  |
++----------+-------+------+
+| Subtotal         |  160 |
++------------------+------+
+| Bolt                    |
++----------+-------+------+
+| Bolt     | Nuts  | 2    |
++----------+-------+------+
+| Subtotal         |    2 |
++------------------+------+
+";
+        assert_eq!(expected, table.to_string());
+        assert!(!table.has_separate_rows());
+
+        // Rows in a group are only separated if the table separates rows.
+        table.set_separate_rows(true);
+        assert!(table.to_string().contains(
+            "| Acme     | Rope  | 10   |\n+----------+-------+------+\n| Acme     | Anvil |"
+        ));
+    }
+}
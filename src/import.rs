@@ -0,0 +1,127 @@
+use crate::{Row, SerdeOptions, Table};
+use anyhow::bail;
+use serde_json::Value;
+
+impl<'data> Table<'data> {
+    /// Read a table from CSV, using the first record as the header.
+    ///
+    /// Records can have different numbers of fields.
+    #[cfg(feature = "csv")]
+    pub fn from_csv_reader(reader: impl std::io::Read) -> anyhow::Result<Self> {
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .from_reader(reader);
+        let mut table = Table::new();
+        for (idx, record) in reader.records().enumerate() {
+            let row = record?
+                .iter()
+                .fold(Row::new(), |row, field| row.with_cell(field.to_owned()));
+            if idx == 0 {
+                table.set_header(row);
+            } else {
+                table.add_row(row);
+            }
+        }
+        Ok(table)
+    }
+
+    /// Build a table from a JSON array of objects or array of arrays.
+    ///
+    /// For objects, there is a column for every key in any object, in the order the keys first
+    /// appear, and the header contains the keys. For arrays, the first array is the header. Nested
+    /// values are written as JSON, and `null`s are empty.
+    pub fn from_json_value(value: Value) -> anyhow::Result<Self> {
+        let Value::Array(items) = value else {
+            bail!("expected a JSON array, found `{value}`");
+        };
+        if items.iter().all(Value::is_object) {
+            Table::from_serde_with(items, &SerdeOptions::new().with_keyed(true))
+        } else if items.iter().all(Value::is_array) {
+            let items = items.into_iter().map(|item| match item {
+                Value::Array(values) => values
+                    .into_iter()
+                    .map(|value| match value {
+                        // `null` would be skipped, which would move the rest of the row left.
+                        Value::Null => Value::String(String::new()),
+                        value => value,
+                    })
+                    .collect(),
+                _ => unreachable!(),
+            });
+            let options = SerdeOptions::new().with_header(false);
+            let mut table = Table::from_serde_with(items.collect::<Vec<Vec<Value>>>(), &options)?;
+            if !table.rows.is_empty() {
+                let header: Row = table.rows.remove(0);
+                table.set_header(header);
+            }
+            Ok(table)
+        } else {
+            bail!("expected a JSON array of objects or an array of arrays")
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Table, TableStyle};
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    fn render(table: Table) -> String {
+        table
+            .with_style(TableStyle::SIMPLE)
+            .with_separate_rows(false)
+            .to_string()
+    }
+
+    #[test]
+    fn from_json_value() {
+        let objects = json!([
+            {"name": "Ann", "age": 31},
+            {"tags": ["a", "b"], "name": "Bob", "age": null},
+        ]);
+        let expected = r#"+------+-----+------------+
+| name | age | tags       |
++------+-----+------------+
+| Ann  |  31 |            |
+| Bob  |     | ["a", "b"] |
++------+-----+------------+
+"#;
+        assert_eq!(expected, render(Table::from_json_value(objects).unwrap()));
+
+        let arrays = json!([["name", "age"], ["Ann", 31], [null, 7]]);
+        let expected = "+------+-----+
+| name | age |
++------+-----+
+| Ann  |  31 |
+|      |   7 |
++------+-----+
+";
+        assert_eq!(expected, render(Table::from_json_value(arrays).unwrap()));
+
+        let err = Table::from_json_value(json!([1, 2])).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "expected a JSON array of objects or an array of arrays"
+        );
+        assert!(Table::from_json_value(json!({"a": 1})).is_err());
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn from_csv_reader() {
+        let csv = "name,age\nAnn,31\n\"Smith, Bob\",7,extra\n";
+        let expected = "+------------+-----+
+| name       | age |
++------------+-----+
+| Ann        | 31  |
+| Smith, Bob | 7   | extra |
++------------+-----+-------+
+";
+        assert_eq!(
+            expected,
+            render(Table::from_csv_reader(csv.as_bytes()).unwrap())
+        );
+    }
+}
@@ -0,0 +1,234 @@
+use crate::{Alignment, Layout, Rendered, Renderer, Row, Table};
+use std::fmt;
+
+/// Formats a table as a LaTeX `tabular` environment.
+///
+/// Cells that span multiple columns, or that are aligned differently to the rest of their column,
+/// are written using `\multicolumn`.
+///
+/// By default, horizontal rules are drawn using `\hline`, following the table's border and
+/// separator settings, and vertical rules are drawn if the table style has a visible vertical
+/// border. When `booktabs` is enabled, the table will use `\toprule`, `\midrule` and
+/// `\bottomrule` from the `booktabs` package instead, with no vertical rules and no rules
+/// between body rows.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Latex {
+    booktabs: bool,
+}
+
+impl Latex {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Whether to use the rules from the `booktabs` package.
+    pub fn with_booktabs(mut self, booktabs: bool) -> Self {
+        self.set_booktabs(booktabs);
+        self
+    }
+
+    /// Whether to use the rules from the `booktabs` package.
+    pub fn set_booktabs(&mut self, booktabs: bool) -> &mut Self {
+        self.booktabs = booktabs;
+        self
+    }
+
+    fn vertical_rules(&self, table: &Table) -> bool {
+        !self.booktabs && table.style.has_visible_vertical()
+    }
+
+    fn rule(&self, booktabs_rule: &str, f: &mut dyn fmt::Write) -> fmt::Result {
+        if self.booktabs {
+            writeln!(f, "{booktabs_rule}")
+        } else {
+            writeln!(f, "\\hline")
+        }
+    }
+
+    fn render_row(
+        &self,
+        row: &Row,
+        alignments: &[Alignment],
+        vrule: &str,
+        f: &mut dyn fmt::Write,
+    ) -> fmt::Result {
+        let mut idx = 0;
+        for cell in row.cells.iter() {
+            if idx > 0 {
+                f.write_str(" & ")?;
+            }
+            let is_multicolumn = cell.col_span > 1
+                || alignments.get(idx).copied().unwrap_or(Alignment::Left) != cell.alignment;
+            if is_multicolumn {
+                let left_rule = if idx == 0 { vrule } else { "" };
+                write!(
+                    f,
+                    "\\multicolumn{{{}}}{{{left_rule}{}{vrule}}}{{",
+                    cell.col_span,
+                    column_spec(cell.alignment)
+                )?;
+                write_escaped(cell.content_for_layout(), f)?;
+                f.write_char('}')?;
+            } else {
+                write_escaped(cell.content_for_layout(), f)?;
+            }
+            idx += cell.col_span;
+        }
+        // Fill in any columns this row is missing.
+        for _ in idx.max(1)..alignments.len() {
+            f.write_str(" & ")?;
+        }
+        writeln!(f, " \\\\")
+    }
+}
+
+impl Renderer for Latex {
+    fn render(&self, table: &Table, _: &Layout, f: &mut dyn fmt::Write) -> fmt::Result {
+        let alignments = table.column_alignments();
+        let vrule = if self.vertical_rules(table) { "|" } else { "" };
+
+        write!(f, "\\begin{{tabular}}{{{vrule}")?;
+        for alignment in alignments.iter() {
+            write!(f, "{}{vrule}", column_spec(*alignment))?;
+        }
+        writeln!(f, "}}")?;
+
+        if table.has_top_border {
+            self.rule("\\toprule", f)?;
+        }
+        if let Some(header) = &table.header {
+            self.render_row(header, &alignments, vrule, f)?;
+            self.rule("\\midrule", f)?;
+        }
+        for (idx, row) in table.rows.iter().enumerate() {
+            let separated = match idx.checked_sub(1).map(|prev| &table.rows[prev]) {
+                None => false,
+                // Group headers and subtotals are ruled off even in booktabs tables.
+                Some(prev) if prev.always_separated || row.always_separated => true,
+                Some(_) => !self.booktabs && table.has_separate_rows && row.has_separator,
+            };
+            if separated {
+                self.rule("\\midrule", f)?;
+            }
+            self.render_row(row, &alignments, vrule, f)?;
+        }
+        if let Some(footer) = &table.footer {
+            self.rule("\\midrule", f)?;
+            self.render_row(footer, &alignments, vrule, f)?;
+        }
+        if table.has_bottom_border {
+            self.rule("\\bottomrule", f)?;
+        }
+        writeln!(f, "\\end{{tabular}}")
+    }
+}
+
+impl<'data> Table<'data> {
+    /// Format the table as a LaTeX `tabular` environment.
+    ///
+    /// Any ANSI escape sequences in cells are removed, and LaTeX special characters are escaped.
+    pub fn to_latex(&self) -> Rendered<'_, Latex> {
+        self.render_with(Latex::new())
+    }
+}
+
+impl Rendered<'_, Latex> {
+    /// Whether to use the rules from the `booktabs` package.
+    pub fn with_booktabs(mut self, booktabs: bool) -> Self {
+        self.renderer.set_booktabs(booktabs);
+        self
+    }
+}
+
+fn column_spec(alignment: Alignment) -> char {
+    match alignment {
+        Alignment::Left => 'l',
+        Alignment::Center => 'c',
+        Alignment::Right => 'r',
+    }
+}
+
+/// Write out text, escaping any characters that have special meaning in LaTeX.
+///
+/// Newlines are replaced with spaces, since they cannot appear in a simple column.
+fn write_escaped(text: &str, f: &mut dyn fmt::Write) -> fmt::Result {
+    for ch in text.chars() {
+        match ch {
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                f.write_char('\\')?;
+                f.write_char(ch)?;
+            }
+            '\\' => f.write_str("\\textbackslash{}")?,
+            '~' => f.write_str("\\textasciitilde{}")?,
+            '^' => f.write_str("\\textasciicircum{}")?,
+            '\n' | '\r' => f.write_char(' ')?,
+            ch => f.write_char(ch)?,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Alignment, Cell, Row, Table, TableStyle};
+    use pretty_assertions::assert_eq;
+
+    fn test_table() -> Table<'static> {
+        Table::new()
+            .with_header(
+                Row::new()
+                    .with_cell("Name")
+                    .with_cell(Cell::from("Cost").with_alignment(Alignment::Right)),
+            )
+            .with_row(
+                Row::new()
+                    .with_cell("Fish & chips")
+                    .with_cell(Cell::from("$5").with_alignment(Alignment::Right)),
+            )
+            .with_row(
+                Row::new()
+                    .with_cell("50% off_peak")
+                    .with_cell(Cell::from("{~^\\}").with_alignment(Alignment::Center)),
+            )
+            .with_row(
+                Row::new().with_cell(
+                    Cell::from("Total")
+                        .with_col_span(2)
+                        .with_alignment(Alignment::Center),
+                ),
+            )
+    }
+
+    #[test]
+    fn tabular() {
+        let expected = r"\begin{tabular}{|l|r|}
+\hline
+Name & Cost \\
+\hline
+Fish \& chips & \$5 \\
+\hline
+50\% off\_peak & \multicolumn{1}{c|}{\{\textasciitilde{}\textasciicircum{}\textbackslash{}\}} \\
+\hline
+\multicolumn{2}{|c|}{Total} \\
+\hline
+\end{tabular}
+";
+        assert_eq!(expected, test_table().to_latex().to_string());
+    }
+
+    #[test]
+    fn booktabs() {
+        let expected = r"\begin{tabular}{lr}
+\toprule
+Name & Cost \\
+\midrule
+Fish \& chips & \$5 \\
+50\% off\_peak & \multicolumn{1}{c}{\{\textasciitilde{}\textasciicircum{}\textbackslash{}\}} \\
+\multicolumn{2}{c}{Total} \\
+\bottomrule
+\end{tabular}
+";
+        let table = test_table().with_style(TableStyle::THIN);
+        assert_eq!(expected, table.to_latex().with_booktabs(true).to_string());
+    }
+}
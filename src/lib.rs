@@ -44,35 +44,60 @@
 //! ║ t is going to wrap to the next line                                             ║
 //! ╚═════════════════════════════════════════════════════════════════════════════════╝
 //!</pre>
+//!
+//!# Features
+//!
+//! - `rayon`: wrap the text in each row in parallel when laying out a table. This can speed up
+//!   rendering tables with many rows. Column widths are still measured on a single thread.
+//! - `csv`: read tables from CSV files using `Table::from_csv_reader`.
 
 #[macro_use]
 extern crate lazy_static;
 
+mod aggregate;
+mod asciidoc;
 mod cell;
+mod de;
+mod group;
+mod import;
+mod latex;
+mod pager;
+mod parse;
+mod render;
 mod row;
+mod rst;
+mod select;
 mod ser;
+mod sort;
 mod style;
+mod svg;
+mod transpose;
 
 pub use crate::{
+    aggregate::{Aggregate, AggregateFn},
+    asciidoc::AsciiDoc,
     cell::{Alignment, Cell},
+    latex::Latex,
+    parse::TableParser,
+    render::{CellLayout, Format, Layout, LayoutCache, Rendered, Renderer, Text, UnknownFormat},
     row::{IntoRow, Row},
+    rst::{RstGrid, RstSimple},
+    ser::{SequenceFormat, SerdeOptions},
+    sort::{Comparator, CompareFn, Order, SortKey},
     style::TableStyle,
+    svg::Svg,
 };
 // TODO just use a serde deserializer.
 #[doc(inline)]
 pub use term_data_table_derive::IntoRow;
 
+use crate::render::CellLines;
 use itertools::Itertools;
-use serde::Serialize;
-use std::{cell::RefCell, collections::HashMap, fmt};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use std::{collections::HashMap, fmt, ops::Range};
 use terminal_size::terminal_size;
 
-thread_local! {
-    /// Used to calculate the maximum width of table cells.
-    static MAX_WIDTHS_CELL_WIDTHS: RefCell<(ColumnWidths, HashMap<usize, usize>)>
-        = RefCell::new((ColumnWidths::new(), HashMap::new()));
-}
-
 /// Represents the vertical position of a row
 #[derive(Eq, PartialEq, Copy, Clone)]
 pub enum RowPosition {
@@ -84,8 +109,14 @@ pub enum RowPosition {
 /// A set of rows containing data
 #[derive(Clone, Debug)]
 pub struct Table<'data> {
+    /// An optional header row, drawn above all other rows.
+    header: Option<Row<'data>>,
     rows: Vec<Row<'data>>,
+    /// An optional footer row, drawn below all other rows.
+    footer: Option<Row<'data>>,
     style: TableStyle,
+    /// The style of the separator above the footer, if different from `style`.
+    footer_style: Option<TableStyle>,
     /// Whether or not to vertically separate rows in the table.
     ///
     /// Defaults to `true`.
@@ -101,24 +132,19 @@ pub struct Table<'data> {
     ///
     /// Defaults to `true`.
     pub has_bottom_border: bool,
-
-    /// Calculated column widths.
-    column_widths: RefCell<ColumnWidths>,
-    /// Calculated row lines
-    row_lines: RefCell<Vec<usize>>,
 }
 
 impl<'data> Default for Table<'data> {
     fn default() -> Self {
         Self {
+            header: None,
             rows: Vec::new(),
+            footer: None,
             style: TableStyle::EXTENDED,
+            footer_style: None,
             has_separate_rows: true,
             has_top_border: true,
             has_bottom_border: true,
-
-            column_widths: RefCell::new(ColumnWidths::new()),
-            row_lines: RefCell::new(vec![]),
         }
     }
 }
@@ -135,12 +161,55 @@ impl<'data> Table<'data> {
         }
     }
 
-    pub fn from_serde(data: impl IntoIterator<Item = impl Serialize>) -> anyhow::Result<Self> {
-        let mut table = Table::new();
-        for row in data {
-            table.add_row(ser::serialize_row(row)?);
-        }
-        Ok(table)
+    /// Set the header row.
+    ///
+    /// The header is always drawn first, and is always separated from the rows below it.
+    pub fn with_header(mut self, header: Row<'data>) -> Self {
+        self.set_header(header);
+        self
+    }
+
+    /// Set the header row.
+    ///
+    /// The header is always drawn first, and is always separated from the rows below it.
+    pub fn set_header(&mut self, header: Row<'data>) -> &mut Self {
+        self.header = Some(header);
+        self
+    }
+
+    /// The header row, if any.
+    pub fn header(&self) -> Option<&Row<'data>> {
+        self.header.as_ref()
+    }
+
+    /// Set the footer row.
+    ///
+    /// The footer is always drawn last, and is always separated from the rows above it. It is not
+    /// moved or removed by sorting, filtering or grouping. See `aggregate_row` for building a row
+    /// of column totals.
+    pub fn with_footer(mut self, footer: Row<'data>) -> Self {
+        self.set_footer(footer);
+        self
+    }
+
+    /// Set the footer row.
+    ///
+    /// The footer is always drawn last, and is always separated from the rows above it. It is not
+    /// moved or removed by sorting, filtering or grouping. See `aggregate_row` for building a row
+    /// of column totals.
+    pub fn set_footer(&mut self, footer: Row<'data>) -> &mut Self {
+        self.footer = Some(footer);
+        self
+    }
+
+    /// The footer row, if any.
+    pub fn footer(&self) -> Option<&Row<'data>> {
+        self.footer.as_ref()
+    }
+
+    /// All rows, not including the header or footer.
+    pub fn rows(&self) -> &[Row<'data>] {
+        &self.rows
     }
 
     /// Add a row
@@ -165,6 +234,33 @@ impl<'data> Table<'data> {
         self
     }
 
+    pub fn style(&self) -> &TableStyle {
+        &self.style
+    }
+
+    /// Use a different style for the separator above the footer.
+    ///
+    /// Only the separator uses this style, the footer row itself uses the table's style.
+    pub fn with_footer_style(mut self, style: TableStyle) -> Self {
+        self.set_footer_style(style);
+        self
+    }
+
+    /// Use a different style for the separator above the footer.
+    ///
+    /// Only the separator uses this style, the footer row itself uses the table's style.
+    pub fn set_footer_style(&mut self, style: TableStyle) -> &mut Self {
+        self.footer_style = Some(style);
+        self
+    }
+
+    /// The style of the separator above the footer.
+    ///
+    /// Defaults to the table's style.
+    pub fn footer_style(&self) -> &TableStyle {
+        self.footer_style.as_ref().unwrap_or(&self.style)
+    }
+
     /*
     pub fn max_column_width(&self) -> usize {
         self.max_column_width
@@ -208,112 +304,221 @@ impl<'data> Table<'data> {
         self
     }
 
-    /// Decide how much space to give each cell and layout the rows.
+    /// The header (if any), followed by all other rows, followed by the footer (if any).
+    pub(crate) fn all_rows(&self) -> impl Iterator<Item = &Row<'data>> + '_ {
+        self.header
+            .iter()
+            .chain(self.rows.iter())
+            .chain(self.footer.iter())
+    }
+
+    /// The maximum number of columns in any row.
+    pub(crate) fn num_columns(&self) -> usize {
+        self.all_rows().map(|row| row.columns()).max().unwrap_or(0)
+    }
+
+    /// The alignment of each column.
     ///
-    /// If no width is given, all cells will be the largest of their contents.
+    /// This is taken from the first cell in each column that only spans that column, preferring
+    /// the body over the header and the header over the footer, and defaulting to
+    /// `Alignment::Left`.
+    pub(crate) fn column_alignments(&self) -> Vec<Alignment> {
+        let mut alignments = vec![None; self.num_columns()];
+        let rows = self.rows.iter().chain(&self.header).chain(&self.footer);
+        for row in rows {
+            let mut idx = 0;
+            for cell in row.cells.iter() {
+                if cell.col_span == 1 && alignments[idx].is_none() {
+                    alignments[idx] = Some(cell.alignment);
+                }
+                idx += cell.col_span;
+            }
+        }
+        alignments
+            .into_iter()
+            .map(|align| align.unwrap_or(Alignment::Left))
+            .collect()
+    }
+
+    /// A copy of the table with only the given rows, keeping the header and footer.
+    pub(crate) fn slice_rows(&self, rows: Range<usize>) -> Table<'data> {
+        Table {
+            header: self.header.clone(),
+            rows: self.rows[rows].to_vec(),
+            footer: self.footer.clone(),
+            style: self.style,
+            footer_style: self.footer_style,
+            has_separate_rows: self.has_separate_rows,
+            has_top_border: self.has_top_border,
+            has_bottom_border: self.has_bottom_border,
+        }
+    }
+
+    /// Decide how much space to give each cell and layout the rows.
     ///
-    fn layout(&self, width: Option<usize>) {
+    /// If no width is given, all cells will be the largest of their contents. If a cache is given,
+    /// it will be used to avoid wrapping cells that haven't changed.
+    pub(crate) fn layout(
+        &self,
+        width: Option<usize>,
+        border_width: usize,
+        cache: Option<&LayoutCache>,
+    ) -> Layout<'_> {
+        let col_widths = self.column_widths(width, border_width);
+        self.layout_with_widths(col_widths, border_width, cache)
+    }
+
+    /// Decide how much space to give each column.
+    pub(crate) fn column_widths(&self, width: Option<usize>, border_width: usize) -> ColumnWidths {
         // We need to know the maxiumum number of columns in a row.
-        let cols = self.rows.iter().map(|row| row.columns()).max().unwrap_or(0);
-        let border_width = self.style.border_width();
-        let mut col_widths = self.column_widths.borrow_mut();
+        let cols = self.num_columns();
+        let rows: Vec<&Row> = self.all_rows().collect();
+        let mut col_widths = ColumnWidths::new();
         col_widths.reset(cols);
 
-        // short-circuit when there are no columns
-        if cols == 0 {
-            return;
-        }
-
-        if let Some(width) = width {
+        // there is no space to share out when there are no columns
+        if let Some(width) = width.filter(|_| cols > 0) {
             // total space available for drawing text
             let cell_width_total = width - (border_width + 1) * cols;
 
-            MAX_WIDTHS_CELL_WIDTHS.with(|lk| {
-                let (ref mut max_widths, ref mut cell_widths) = &mut *lk.borrow_mut();
-                // reset
-                max_widths.reset(cols);
-                cell_widths.clear();
+            let mut max_widths = ColumnWidths::new();
+            max_widths.reset(cols);
+            let mut cell_widths = HashMap::new();
 
-                // first stash the max space each column will need.
-                for row in self.rows.iter() {
-                    max_widths.fit_row_singleline(row, border_width);
-                }
+            // first stash the max space each column will need.
+            max_widths.fit_rows_singleline(&rows, border_width);
 
-                // Next, calculate the width we would give each cell if we were splitting space
-                // evenly
-                let cell_width = cell_width_total / cols;
+            // Next, calculate the width we would give each cell if we were splitting space
+            // evenly
+            let cell_width = cell_width_total / cols;
 
-                // Next, find all cells with max width less than the cell width we calculated and
-                // give them their max width
-                for (idx, max_width) in max_widths.iter().enumerate() {
-                    if *max_width < cell_width {
-                        cell_widths.insert(idx, *max_width);
-                    }
+            // Next, find all cells with max width less than the cell width we calculated and
+            // give them their max width
+            for (idx, max_width) in max_widths.iter().enumerate() {
+                if *max_width < cell_width {
+                    cell_widths.insert(idx, *max_width);
                 }
+            }
 
-                let remaining_cells = cols - cell_widths.len();
-                let remaining_space =
-                    cell_width_total - cell_widths.values().copied().sum::<usize>();
-                if remaining_cells > 0 {
-                    let cell_width = remaining_space / remaining_cells;
-                    for idx in 0..cols {
-                        cell_widths.entry(idx).or_insert(cell_width);
-                    }
+            let remaining_cells = cols - cell_widths.len();
+            let remaining_space = cell_width_total - cell_widths.values().copied().sum::<usize>();
+            if let Some(cell_width) = remaining_space.checked_div(remaining_cells) {
+                for idx in 0..cols {
+                    cell_widths.entry(idx).or_insert(cell_width);
                 }
+            }
 
-                col_widths.from_map(cell_widths);
-            });
+            col_widths.set_from_map(&cell_widths);
         } else {
             // Give all cells all the space they need.
-            for row in self.rows.iter() {
-                col_widths.fit_row_singleline(row, border_width);
-            }
+            col_widths.fit_rows_singleline(&rows, border_width);
+        }
+        col_widths
+    }
+
+    /// Layout the rows using the given column widths.
+    pub(crate) fn layout_with_widths(
+        &self,
+        col_widths: ColumnWidths,
+        border_width: usize,
+        cache: Option<&LayoutCache>,
+    ) -> Layout<'_> {
+        let rows: Vec<&Row> = self.all_rows().collect();
+        let cached = cache.map(LayoutCache::take);
+        let cell_lines: &CellLines = &|cell, width| {
+            cached
+                .as_ref()
+                .and_then(|cached| cached.get(&(cell.revision, width)).cloned())
+                .unwrap_or_else(|| cell.layout(Some(width)).into())
+        };
+        // Rows are wrapped independently, so this gives the same result in parallel.
+        #[cfg(feature = "rayon")]
+        let cells: Vec<_> = rows
+            .into_par_iter()
+            .map(|row| row.layout(&col_widths, border_width, cell_lines))
+            .collect();
+        #[cfg(not(feature = "rayon"))]
+        let cells: Vec<_> = rows
+            .into_iter()
+            .map(|row| row.layout(&col_widths, border_width, cell_lines))
+            .collect();
+        if let Some(cache) = cache {
+            cache.store(&cells);
         }
-        for row in self.rows.iter() {
-            self.row_lines
-                .borrow_mut()
-                .push(row.layout(&*col_widths, border_width));
+        let row_lines = cells
+            .iter()
+            .map(|row| row.iter().map(|cell| cell.lines.len()).max().unwrap_or(0))
+            .collect();
+        Layout {
+            column_widths: col_widths.0,
+            row_lines,
+            cells,
         }
     }
 
-    /// Write the table out to a formatter.
+    /// Decide how much space to give each cell and layout the rows, using the table's style.
     ///
-    /// This method calculates stale state that it needs.
-    ///
-    /// # Params
-    ///  - `view_width` - the width of the viewport we are rendering to, if any. If unspecified,
-    ///    we will assume infinite width.
-    fn render(&self, view_width: Option<usize>, f: &mut fmt::Formatter) -> fmt::Result {
-        self.layout(view_width);
-        if self.rows.is_empty() {
-            return writeln!(f, "<empty table>");
+    /// If no width is given, all cells will be the largest of their contents. This is the layout
+    /// used when displaying the table.
+    pub fn compute_layout(&self, width: Option<usize>) -> Layout<'_> {
+        self.layout(width, self.style.border_width(), None)
+    }
+
+    /// Like `compute_layout`, but reuse the layout of cells that haven't changed since the cache
+    /// was last used.
+    pub fn compute_layout_cached(&self, width: Option<usize>, cache: &LayoutCache) -> Layout<'_> {
+        self.layout(width, self.style.border_width(), Some(cache))
+    }
+
+    /// The settings used when drawing this table with box characters.
+    pub(crate) fn box_style(&self) -> BoxStyle {
+        BoxStyle {
+            style: self.style,
+            header_style: self.style,
+            footer_style: *self.footer_style(),
+            has_separate_rows: self.has_separate_rows,
+            has_top_border: self.has_top_border,
+            has_bottom_border: self.has_bottom_border,
         }
-        let row_lines = self.row_lines.borrow();
+    }
 
-        if self.has_top_border {
-            self.rows[0].render_top_separator(&*self.column_widths.borrow(), &self.style, f)?;
+    /// Write the table out using the given box drawing settings.
+    pub(crate) fn render_box(
+        &self,
+        box_style: &BoxStyle,
+        layout: &Layout,
+        f: &mut dyn fmt::Write,
+    ) -> fmt::Result {
+        let style = &box_style.style;
+        let rows: Vec<&Row> = self.all_rows().collect();
+        if rows.is_empty() {
+            return writeln!(f, "<empty table>");
         }
-        self.rows[0].render_content(&*self.column_widths.borrow(), row_lines[0], &self.style, f)?;
+        let row_lines = &layout.row_lines;
+        let column_widths = &layout.column_widths;
 
-        for (idx, (prev_row, row)) in self.rows.iter().tuple_windows().enumerate() {
-            if self.has_separate_rows {
-                row.render_separator(prev_row, &*self.column_widths.borrow(), &self.style, f)?;
+        if box_style.has_top_border {
+            rows[0].render_top_separator(column_widths, style, f)?;
+        }
+        Row::render_content(&layout.cells[0], row_lines[0], style, f)?;
+
+        // `rows` isn't empty, and the footer (if any) is last.
+        let footer_idx = self.footer.as_ref().map(|_| rows.len() - 1);
+        for (idx, (prev_row, row)) in rows.iter().tuple_windows().enumerate() {
+            // The header and footer are always separated from the body.
+            if Some(idx + 1) == footer_idx {
+                row.render_separator(prev_row, column_widths, &box_style.footer_style, f)?;
+            } else if idx == 0 && self.header.is_some() {
+                row.render_separator(prev_row, column_widths, &box_style.header_style, f)?;
+            } else if prev_row.always_separated || row.always_separated {
+                row.write_separator(prev_row, column_widths, style, f)?;
+            } else if box_style.has_separate_rows {
+                row.render_separator(prev_row, column_widths, style, f)?;
             }
-
-            let row_lines = self.row_lines.borrow();
-            row.render_content(
-                &*self.column_widths.borrow(),
-                row_lines[idx + 1],
-                &self.style,
-                f,
-            )?;
+            Row::render_content(&layout.cells[idx + 1], row_lines[idx + 1], style, f)?;
         }
-        if self.has_bottom_border {
-            self.rows[self.rows.len() - 1].render_bottom_separator(
-                &*self.column_widths.borrow(),
-                &self.style,
-                f,
-            )?;
+        if box_style.has_bottom_border {
+            rows[rows.len() - 1].render_bottom_separator(column_widths, style, f)?;
         }
         Ok(())
     }
@@ -324,41 +529,71 @@ impl<'data> Table<'data> {
     ///
     /// Will panic if it cannot get the terminal width (e.g. because we aren't in a terminal).
     pub fn for_terminal(&self) -> impl fmt::Display + '_ {
-        match terminal_size().and_then(|v| usize::try_from((v.0).0).ok()) {
-            Some(width) => FixedWidth { table: self, width },
-            None => FixedWidth {
-                table: self,
-                width: usize::MAX,
-            },
-        }
+        let width = terminal_size().map(|v| usize::from((v.0).0));
+        self.fixed_width(width.unwrap_or(usize::MAX))
     }
 
     /// Use a custom value for the table width
     pub fn fixed_width(&self, width: usize) -> impl fmt::Display + '_ {
-        FixedWidth { table: self, width }
+        self.render_with(Text).with_width(width)
     }
-}
 
-struct FixedWidth<'a> {
-    table: &'a Table<'a>,
-    width: usize,
-}
+    /// Write out the table using the given renderer.
+    ///
+    /// The returned value implements `Display`. By default the table is as wide as its contents;
+    /// use `Rendered::with_width` to wrap it to a given width.
+    pub fn render_with<R: Renderer>(&self, renderer: R) -> Rendered<'_, R> {
+        Rendered::new(self, renderer)
+    }
 
-impl fmt::Display for FixedWidth<'_> {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        self.table.render(Some(self.width), f)
+    /// Write out some of the rows, with the header and footer (if any).
+    ///
+    /// Columns are sized to fit the whole table, so they are the same width whichever rows are
+    /// written. Rows past the end of the table are ignored. See `Rendered::with_rows` for more.
+    pub fn render_rows(&self, rows: Range<usize>) -> Rendered<'_, Text> {
+        self.render_with(Text).with_rows(rows)
+    }
+
+    /// Write out a page of rows, with the header and footer (if any).
+    ///
+    /// Pages are numbered from 0. See `render_rows` for details.
+    pub fn render_page(&self, page: usize, page_size: usize) -> Rendered<'_, Text> {
+        self.render_with(Text).with_page(page, page_size)
+    }
+
+    /// The number of pages needed to show all rows, with `page_size` rows on each page.
+    pub fn num_pages(&self, page_size: usize) -> usize {
+        self.rows.len().div_ceil(page_size.max(1))
+    }
+
+    /// Write out the table in one of the built-in formats.
+    pub fn to_format(&self, format: Format) -> Rendered<'_, Box<dyn Renderer>> {
+        self.render_with(format.renderer())
     }
 }
 
+/// The parts of a table's appearance used when drawing it with box characters.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct BoxStyle {
+    pub(crate) style: TableStyle,
+    /// The style used for the separator between the header and the body.
+    pub(crate) header_style: TableStyle,
+    /// The style used for the separator between the body and the footer.
+    pub(crate) footer_style: TableStyle,
+    pub(crate) has_separate_rows: bool,
+    pub(crate) has_top_border: bool,
+    pub(crate) has_bottom_border: bool,
+}
+
 impl<'data> fmt::Display for Table<'data> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        self.render(None, f)
+        self.render_with(Text).fmt(f)
     }
 }
 
-pub fn data_table<'a, R: 'a>(input: impl IntoIterator<Item = &'a R>) -> Table<'a>
+pub fn data_table<'a, R>(input: impl IntoIterator<Item = &'a R>) -> Table<'a>
 where
-    R: IntoRow,
+    R: IntoRow + 'a,
 {
     let mut table = Table::new();
     for row in input {
@@ -367,8 +602,25 @@ where
     table
 }
 
+/// Build a table showing a single record, with a row for each field containing its header and
+/// value.
+///
+/// See `Table::from_serde_record` for a version that shows nested structs as sections.
+pub fn record_table<R: IntoRow>(record: &R) -> Table<'_> {
+    let mut table = Table::new().with_separate_rows(false);
+    for (header, value) in record
+        .headers()
+        .cells
+        .into_iter()
+        .zip(record.into_row().cells)
+    {
+        table.add_row(Row::new().with_cell(header).with_cell(value));
+    }
+    table
+}
+
 #[derive(Debug, Clone)]
-struct ColumnWidths(Vec<usize>);
+pub(crate) struct ColumnWidths(Vec<usize>);
 
 impl ColumnWidths {
     fn new() -> Self {
@@ -381,33 +633,34 @@ impl ColumnWidths {
         self.0.resize(num_cols, 0);
     }
 
-    /// Make our widths fit the given row with all text on a single line.
+    /// Make our widths fit the given rows with all text on a single line.
     ///
     /// This is for when we are allowed to use as much space as we want.
-    fn fit_row_singleline(&mut self, row: &Row, border_width: usize) {
-        let mut idx = 0;
-        for cell in row.cells.iter() {
+    fn fit_rows_singleline(&mut self, rows: &[&Row], border_width: usize) {
+        for (idx, cell) in rows.iter().flat_map(|row| row.cells_with_columns()) {
             if cell.col_span == 1 {
-                self.0[idx] = self.0[idx].max(cell.min_width(true));
-            } else {
-                // space required to fit this cell (taking into account we have some borders to
-                // use).
-                let required_width = cell.min_width(true) - border_width * (cell.col_span - 1);
-                let floor_per_cell = required_width / cell.col_span;
-                // space we need to put somewhere
-                let mut to_fit = required_width % cell.col_span;
-                // split space evenly, with remainder in last space.
-                for i in 0..cell.col_span {
-                    let extra = if to_fit > 0 { 1 } else { 0 };
-                    to_fit = to_fit.saturating_sub(1);
-                    self.0[idx + i] = self.0[idx + 1].max(floor_per_cell + extra);
-                }
+                self.0[idx] = self.0[idx].max(cell.min_width());
+            }
+        }
+        // Cells spanning multiple columns are fitted after all other cells, so they only widen
+        // columns if they don't already fit.
+        for (idx, cell) in rows.iter().flat_map(|row| row.cells_with_columns()) {
+            if cell.col_span == 1 {
+                continue;
+            }
+            let columns = &mut self.0[idx..idx + cell.col_span];
+            // space we already have, including the borders we span over.
+            let width = columns.iter().sum::<usize>() + border_width * (cell.col_span - 1);
+            // space we need to put somewhere
+            let to_fit = cell.min_width().saturating_sub(width);
+            // split space evenly, with remainder in the first columns.
+            for (i, column) in columns.iter_mut().enumerate() {
+                *column += to_fit / cell.col_span + usize::from(i < to_fit % cell.col_span);
             }
-            idx += cell.col_span;
         }
     }
 
-    fn from_map(&mut self, map: &HashMap<usize, usize>) {
+    fn set_from_map(&mut self, map: &HashMap<usize, usize>) {
         for (idx, slot) in self.0.iter_mut().enumerate() {
             *slot = *map.get(&idx).unwrap();
         }
@@ -430,6 +683,43 @@ mod test {
     use crate::TableStyle;
     use pretty_assertions::assert_eq;
 
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel_layout_matches_serial() {
+        let mut table = Table::new();
+        for idx in 0..100 {
+            table.add_row(
+                Row::new()
+                    .with_cell(idx.to_string())
+                    .with_cell("text that needs wrapping ".repeat(idx % 4)),
+            );
+        }
+        let border_width = table.style.border_width();
+        let lines = |layout: &[Vec<crate::CellLayout>]| -> Vec<Vec<(usize, Vec<String>)>> {
+            layout
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .map(|cell| (cell.width(), cell.lines().map(String::from).collect()))
+                        .collect()
+                })
+                .collect()
+        };
+
+        let layout = table.layout(Some(30), border_width, None);
+        let col_widths = table.column_widths(Some(30), border_width);
+        let serial: Vec<_> = table
+            .all_rows()
+            .map(|row| {
+                row.layout(&col_widths, border_width, &|cell, width| {
+                    cell.layout(Some(width)).into()
+                })
+            })
+            .collect();
+        assert!(layout.row_lines().iter().any(|lines| *lines > 1));
+        assert_eq!(lines(layout.cells()), lines(&serial));
+    }
+
     #[test]
     fn correct_default_padding() {
         let table = Table::new()
@@ -525,10 +815,168 @@ mod test {
 +-----------------------------------------------------------------------------+
 ";
         let table = table.fixed_width(80);
-        println!("{}", table.to_string());
+        println!("{}", table);
         assert_eq!(expected, table.to_string());
     }
 
+    #[test]
+    fn header_always_separated() {
+        let table = Table::new()
+            .with_separate_rows(false)
+            .with_style(TableStyle::SIMPLE)
+            .with_header(Row::new().with_cell("Name").with_cell("Age"))
+            .with_row(Row::new().with_cell("John").with_cell("28"))
+            .with_row(Row::new().with_cell("Jane").with_cell("72"));
+        let expected = r"+------+-----+
+| Name | Age |
++------+-----+
+| John | 28  |
+| Jane | 72  |
++------+-----+
+";
+        println!("{}", table);
+        assert_eq!(expected, table.to_string());
+    }
+
+    #[test]
+    fn colored_text_wraps() {
+        let table = Table::new().with_style(TableStyle::SIMPLE).with_row(
+            Row::new()
+                .with_cell("\x1b[31mred text\x1b[0m plain")
+                .with_cell("b"),
+        );
+        let expected = "+---------+---+
+| \x1b[31mred\x1b[0m     | b |
+| \x1b[31mtext\x1b[0m    |   |
+| plain   |   |
++---------+---+
+";
+        assert_eq!(expected, table.fixed_width(16).to_string());
+    }
+
+    #[test]
+    fn wrap_long_word() {
+        let table = Table::new().with_style(TableStyle::SIMPLE).with_row(
+            Row::new()
+                .with_cell("")
+                .with_cell("{\"id\":1,\"name\":\"item\"}, ok"),
+        );
+        let expected = r#"+--+----------+
+|  | {"id":1, |
+|  | "name":" |
+|  | item"},  |
+|  | ok       |
++--+----------+
+"#;
+        assert_eq!(expected, table.fixed_width(16).to_string());
+    }
+
+    #[test]
+    fn layout_rows_independently() {
+        let table = Table::from_rows(
+            (0..500)
+                .map(|idx| {
+                    Row::new()
+                        .with_cell(idx.to_string())
+                        .with_cell("some text ".repeat(idx % 7))
+                        .with_cell(Cell::from("x".repeat(idx % 13)).with_col_span(idx % 2 + 1))
+                })
+                .collect(),
+        );
+        let cache = crate::LayoutCache::new();
+        for width in [30, 50, 30] {
+            let layout = table.compute_layout_cached(Some(width), &cache);
+            assert_eq!(layout.cells().len(), table.rows().len());
+            for (row, cells) in table.rows().iter().zip(layout.cells()) {
+                let expected = row.layout(
+                    layout.column_widths(),
+                    table.style().border_width(),
+                    &|cell, width| cell.layout(Some(width)).into(),
+                );
+                let lines = |cells: &[crate::CellLayout]| {
+                    cells
+                        .iter()
+                        .map(|cell| (cell.width(), cell.lines().map(String::from).collect()))
+                        .collect::<Vec<(usize, Vec<String>)>>()
+                };
+                assert_eq!(lines(&expected), lines(cells));
+            }
+        }
+    }
+
+    #[test]
+    fn table_is_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Table>();
+        assert_send_sync::<crate::Layout>();
+    }
+
+    #[test]
+    fn render_at_different_widths() {
+        let table = Table::new()
+            .with_style(TableStyle::SIMPLE)
+            .with_header(Row::new().with_cell("Name").with_cell("Notes"))
+            .with_row(Row::new().with_cell("a").with_cell("some longer text"));
+        let narrow = "+------+--------+
+| Name | Notes  |
++------+--------+
+| a    | some   |
+|      | longer |
+|      | text   |
++------+--------+
+";
+        let wide = "+------+------------------+
+| Name | Notes            |
++------+------------------+
+| a    | some longer text |
++------+------------------+
+";
+        assert_eq!(narrow, table.fixed_width(18).to_string());
+        assert_eq!(wide, table.to_string());
+        assert_eq!(narrow, table.fixed_width(18).to_string());
+
+        let table = std::sync::Arc::new(table);
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let table = table.clone();
+                std::thread::spawn(move || table.fixed_width(18).to_string())
+            })
+            .collect();
+        for handle in handles {
+            assert_eq!(narrow, handle.join().unwrap());
+        }
+    }
+
+    #[test]
+    fn compute_layout() {
+        let table = Table::new()
+            .with_style(TableStyle::SIMPLE)
+            .with_header(Row::new().with_cell("Name").with_cell("Notes"))
+            .with_row(
+                Row::new()
+                    .with_cell("a")
+                    .with_cell("\x1b[31mred text\x1b[0m plain"),
+            );
+        let layout = table.compute_layout(Some(18));
+        assert_eq!(layout.column_widths(), &[6, 8]);
+        assert_eq!(layout.row_lines(), &[1, 3]);
+        let cells = layout.cells();
+        assert_eq!(cells.len(), 2);
+        assert_eq!(cells[1][0].width(), 6);
+        assert_eq!(cells[1][0].lines().collect::<Vec<_>>(), vec!["a"]);
+        assert_eq!(
+            cells[1][1].lines().collect::<Vec<_>>(),
+            vec!["red", "text", "plain"]
+        );
+        assert_eq!(
+            cells[1][1].cell().content(),
+            "\x1b[31mred text\x1b[0m plain"
+        );
+
+        let layout = table.compute_layout(None);
+        assert_eq!(layout.row_lines(), &[1, 1]);
+    }
+
     #[test]
     #[ignore]
     fn uneven_with_varying_col_span() {
@@ -567,7 +1015,7 @@ mod test {
 +----------+-----+
 | 2        | 10  |
 +----------+-----+
-|\03\0         | 100 |
+|\x003\0         | 100 |
 +----------+-----+
 |        S       |
 +----------------+
@@ -610,13 +1058,13 @@ mod test {
                         .with_alignment(Alignment::Center),
                 ),
             );
-        let expected = "+-----+-----+
-|  A  |  B  |
-| 1   | 1   |
-| 2   | 10  |
-| 3   | 100 |
-|  Spanner  |
-+-----------+
+        let expected = "+---+-----+
+| A |  B  |
+| 1 | 1   |
+| 2 | 10  |
+| 3 | 100 |
+| Spanner |
++---------+
 ";
         println!("{}", table);
         assert_eq!(expected.trim(), table.to_string().trim());
@@ -998,6 +1446,38 @@ mod test {
             }
         */
 
+    #[test]
+    fn record_table() {
+        struct Person {
+            name: &'static str,
+            age: u32,
+        }
+
+        impl crate::IntoRow for Person {
+            fn headers(&self) -> Row<'_> {
+                Row::new().with_cell("name").with_cell("age")
+            }
+
+            fn into_row(&self) -> Row<'_> {
+                Row::new()
+                    .with_cell(self.name)
+                    .with_cell(self.age.to_string())
+            }
+        }
+
+        let person = Person {
+            name: "Ann",
+            age: 31,
+        };
+        let table = crate::record_table(&person).with_style(TableStyle::SIMPLE);
+        let expected = "+------+-----+
+| name | Ann |
+| age  | 31  |
++------+-----+
+";
+        assert_eq!(expected, table.to_string());
+    }
+
     fn add_data_to_test_table(table: &mut Table) {
         table.add_row(
             Row::new().with_cell(
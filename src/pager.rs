@@ -0,0 +1,129 @@
+use crate::Table;
+use std::{
+    env,
+    io::{self, IsTerminal, Write},
+    process::{Command, Stdio},
+};
+use terminal_size::terminal_size;
+
+/// The pager used when `$PAGER` isn't set. `-S` lets wide tables scroll sideways instead of
+/// wrapping, and `-R` keeps colors.
+const DEFAULT_PAGER: &str = "less -RS";
+
+impl<'data> Table<'data> {
+    /// Print the table to stdout, using a pager if it is taller than the terminal.
+    ///
+    /// When printed directly to a terminal, the table is wrapped to its width. In the pager it is
+    /// shown at its natural width, so wide tables can be scrolled sideways.
+    ///
+    /// The pager is taken from `$PAGER`, defaulting to `less -RS`. If stdout isn't a terminal,
+    /// `$PAGER` is empty, or the pager can't be started, the table is printed directly.
+    pub fn print_paged(&self) -> io::Result<()> {
+        let size = terminal_size().map(|(width, height)| (width.0.into(), height.0.into()));
+        let (text, fallback) = match output(self, io::stdout().is_terminal(), size) {
+            Output::Print(text) => return io::stdout().write_all(text.as_bytes()),
+            Output::Page { text, fallback } => (text, fallback),
+        };
+        let pager = env::var("PAGER").unwrap_or_else(|_| DEFAULT_PAGER.into());
+        match pager_command(&pager).map(|mut cmd| cmd.stdin(Stdio::piped()).spawn()) {
+            Some(Ok(mut child)) => {
+                let written = child.stdin.take().unwrap().write_all(text.as_bytes());
+                child.wait()?;
+                match written {
+                    // The user quit the pager before reading everything.
+                    Err(e) if e.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+                    written => written,
+                }
+            }
+            _ => io::stdout().write_all(fallback.as_bytes()),
+        }
+    }
+}
+
+/// What `print_paged` shows.
+#[derive(Debug, PartialEq, Eq)]
+enum Output {
+    /// Print the text directly.
+    Print(String),
+    /// Show the text in a pager, or print `fallback` if there is no pager.
+    Page { text: String, fallback: String },
+}
+
+/// Decide how to show a table, given whether stdout is a terminal and the terminal's width and
+/// height.
+fn output(table: &Table, is_tty: bool, size: Option<(usize, usize)>) -> Output {
+    let Some((width, height)) = size.filter(|_| is_tty) else {
+        return Output::Print(table.to_string());
+    };
+    let wrapped = table.fixed_width(width).to_string();
+    if wrapped.lines().count() < height {
+        return Output::Print(wrapped);
+    }
+    Output::Page {
+        text: table.to_string(),
+        fallback: wrapped,
+    }
+}
+
+/// Build the command to run a pager, e.g. `less -RS`.
+///
+/// Returns `None` if there is no pager.
+fn pager_command(pager: &str) -> Option<Command> {
+    let mut args = pager.split_whitespace();
+    let mut cmd = Command::new(args.next()?);
+    cmd.args(args);
+    Some(cmd)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{output, pager_command, Output};
+    use crate::{Row, Table, TableStyle};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn pager_commands() {
+        let cmd = pager_command(" less  -RS ").unwrap();
+        assert_eq!(cmd.get_program(), "less");
+        assert_eq!(cmd.get_args().collect::<Vec<_>>(), vec!["-RS"]);
+        assert!(pager_command("").is_none());
+    }
+
+    #[test]
+    fn outputs() {
+        let table = Table::new()
+            .with_style(TableStyle::SIMPLE)
+            .with_separate_rows(false)
+            .with_row(Row::new().with_cell("a").with_cell("some long text"))
+            .with_row(Row::new().with_cell("b").with_cell("more"));
+        let natural = "+---+----------------+
+| a | some long text |
+| b | more           |
++---+----------------+
+";
+        let wrapped = "+---+------+
+| a | some |
+|   | long |
+|   | text |
+| b | more |
++---+------+
+";
+        assert_eq!(
+            output(&table, false, Some((13, 50))),
+            Output::Print(natural.into())
+        );
+        assert_eq!(output(&table, true, None), Output::Print(natural.into()));
+        assert_eq!(
+            output(&table, true, Some((13, 50))),
+            Output::Print(wrapped.into())
+        );
+        // Wrapped, the table is as tall as the terminal, so it is paged at its natural width.
+        assert_eq!(
+            output(&table, true, Some((13, 6))),
+            Output::Page {
+                text: natural.into(),
+                fallback: wrapped.into(),
+            }
+        );
+    }
+}
@@ -0,0 +1,316 @@
+use crate::{cell::ANSI_ESC_RE, Cell, Row, Table};
+use anyhow::{anyhow, bail};
+use unicode_width::UnicodeWidthChar;
+
+/// The vertical borders of the built-in styles.
+const VERTICALS: [char; 4] = ['|', '│', '║', '\0'];
+
+/// Every character used for borders and separators in the built-in styles, including the `=`
+/// used under headers in reStructuredText.
+const BORDER_CHARS: &str = "+-=|─│┌┐└┘├┤┬┴┼╭╮╰╯═║╔╗╚╝╠╣╦╩╬\0";
+
+/// Characters that fill in separators between borders.
+const FILL_CHARS: [char; 5] = ['-', '=', '─', '═', '\0'];
+
+/// Reads tables that have been written out as text back into rows of cells.
+///
+/// Tables drawn with any of the built-in styles can be read, apart from `TableStyle::EMPTY`,
+/// whose borders can't be told apart from the text. Any ANSI escape sequences are removed, and
+/// the text of cells that was wrapped over several lines is joined with spaces.
+#[derive(Debug, Clone)]
+pub struct TableParser {
+    header: bool,
+    separate_rows: bool,
+}
+
+impl Default for TableParser {
+    fn default() -> Self {
+        Self {
+            header: true,
+            separate_rows: true,
+        }
+    }
+}
+
+impl TableParser {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn header(&self) -> bool {
+        self.header
+    }
+
+    /// Whether the first row is a header.
+    ///
+    /// Defaults to `true`.
+    pub fn with_header(mut self, header: bool) -> Self {
+        self.set_header(header);
+        self
+    }
+
+    /// Whether the first row is a header.
+    ///
+    /// Defaults to `true`.
+    pub fn set_header(&mut self, header: bool) -> &mut Self {
+        self.header = header;
+        self
+    }
+
+    pub fn separate_rows(&self) -> bool {
+        self.separate_rows
+    }
+
+    /// Whether rows are separated by lines, so each row can be wrapped over several lines.
+    ///
+    /// Set this to `false` to read tables drawn without separate rows, where every line is a
+    /// row. Defaults to `true`.
+    pub fn with_separate_rows(mut self, separate_rows: bool) -> Self {
+        self.set_separate_rows(separate_rows);
+        self
+    }
+
+    /// Whether rows are separated by lines, so each row can be wrapped over several lines.
+    ///
+    /// Set this to `false` to read tables drawn without separate rows, where every line is a
+    /// row. Defaults to `true`.
+    pub fn set_separate_rows(&mut self, separate_rows: bool) -> &mut Self {
+        self.separate_rows = separate_rows;
+        self
+    }
+
+    /// Read a table from text.
+    pub fn parse(&self, text: &str) -> anyhow::Result<Table<'static>> {
+        let text = ANSI_ESC_RE.replace_all(text, "");
+        let lines: Vec<&str> = text.lines().filter(|line| !line.is_empty()).collect();
+        if lines.is_empty() || lines == ["<empty table>"] {
+            return Ok(Table::new());
+        }
+
+        // Group the lines between separators, and find where columns start and end.
+        let mut groups: Vec<Vec<&str>> = vec![];
+        let mut in_group = false;
+        let mut separator_junctions = vec![];
+        let mut verticals = vec![];
+        for (idx, line) in lines.iter().enumerate() {
+            if line.chars().all(|ch| BORDER_CHARS.contains(ch)) {
+                separator_junctions
+                    .extend(char_positions(line).filter(|(_, ch)| !FILL_CHARS.contains(ch)));
+                in_group = false;
+                continue;
+            }
+            match line.chars().next() {
+                Some(ch) if VERTICALS.contains(&ch) => {
+                    verticals.extend(char_positions(line).filter(|(_, c)| *c == ch));
+                }
+                Some(ch) => bail!(
+                    "line {}: unknown table border `{}`",
+                    idx + 1,
+                    ch.escape_debug()
+                ),
+                None => unreachable!(),
+            }
+            if !in_group {
+                groups.push(vec![]);
+                in_group = true;
+            }
+            groups.last_mut().unwrap().push(line);
+        }
+        // Separators show every column boundary, but content lines may have `|` in cells.
+        let mut boundaries: Vec<usize> = if separator_junctions.is_empty() {
+            verticals.into_iter().map(|(pos, _)| pos).collect()
+        } else {
+            separator_junctions
+                .into_iter()
+                .map(|(pos, _)| pos)
+                .collect()
+        };
+        boundaries.sort_unstable();
+        boundaries.dedup();
+
+        // The header is always separated from the body, so it can be wrapped even if the rows
+        // aren't separated.
+        let header_group = self.header && groups.len() > 1;
+        let mut rows = vec![];
+        for (idx, group) in groups.into_iter().enumerate() {
+            if self.separate_rows || (header_group && idx == 0) {
+                rows.push(parse_row(&group, &boundaries));
+            } else {
+                rows.extend(
+                    group
+                        .into_iter()
+                        .map(|line| parse_row(&[line], &boundaries)),
+                );
+            }
+        }
+        let rows = rows.into_iter().collect::<anyhow::Result<Vec<_>>>()?;
+        let mut rows = rows.into_iter();
+        let mut table = Table::new();
+        if self.header {
+            if let Some(header) = rows.next() {
+                table.set_header(header);
+            }
+        }
+        for row in rows {
+            table.add_row(row);
+        }
+        Ok(table)
+    }
+}
+
+impl<'data> Table<'data> {
+    /// Read a table from text, with the first row as a header.
+    ///
+    /// See `TableParser` for details.
+    pub fn parse(text: &str) -> anyhow::Result<Table<'static>> {
+        TableParser::new().parse(text)
+    }
+}
+
+/// The position (in columns on screen) of each character in a line.
+fn char_positions(line: &str) -> impl Iterator<Item = (usize, char)> + '_ {
+    line.chars().scan(0, |pos, ch| {
+        let start = *pos;
+        *pos += ch.width().unwrap_or(0);
+        Some((start, ch))
+    })
+}
+
+/// Split a line into the text of each cell, with the number of columns it spans.
+fn split_line<'a>(line: &'a str, boundaries: &[usize]) -> Vec<(&'a str, usize)> {
+    let vertical = line.chars().next().unwrap();
+    let mut cells = vec![];
+    let mut start = (0, vertical.len_utf8());
+    for ((pos, ch), (offset, _)) in char_positions(line).zip(line.char_indices()).skip(1) {
+        if ch != vertical || boundaries.binary_search(&pos).is_err() {
+            continue;
+        }
+        let span = boundaries
+            .iter()
+            .filter(|boundary| (start.0 + 1..pos).contains(boundary))
+            .count()
+            + 1;
+        cells.push((&line[start.1..offset], span));
+        start = (pos, offset + ch.len_utf8());
+    }
+    cells
+}
+
+/// Join the lines of a row, which should all have the same cells.
+fn parse_row(lines: &[&str], boundaries: &[usize]) -> anyhow::Result<Row<'static>> {
+    let mut cells: Vec<(Vec<&str>, usize)> = vec![];
+    for line in lines {
+        let line_cells = split_line(line, boundaries);
+        if cells.is_empty() {
+            cells = line_cells.iter().map(|(_, span)| (vec![], *span)).collect();
+        }
+        if line_cells.len() != cells.len() {
+            return Err(anyhow!("row has lines with different cells: `{line}`"));
+        }
+        for ((lines, _), (text, _)) in cells.iter_mut().zip(line_cells) {
+            let text = text.trim();
+            if !text.is_empty() {
+                lines.push(text);
+            }
+        }
+    }
+    let mut row = Row::new();
+    for (lines, col_span) in cells {
+        row.add_cell(Cell::from(lines.join(" ")).with_col_span(col_span));
+    }
+    Ok(row)
+}
+
+#[cfg(test)]
+mod test {
+    use super::TableParser;
+    use crate::{Cell, Row, Table, TableStyle};
+    use pretty_assertions::assert_eq;
+
+    fn cells(table: &Table) -> Vec<Vec<(String, usize)>> {
+        table
+            .header()
+            .into_iter()
+            .chain(table.rows())
+            .map(|row| {
+                row.cells()
+                    .iter()
+                    .map(|cell| (cell.content().to_string(), cell.col_span()))
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parse_styles() {
+        let table = Table::new()
+            .with_header(Row::new().with_cell("name").with_cell("age").with_cell("x"))
+            .with_row(
+                Row::new()
+                    .with_cell("Ann has a name that wraps")
+                    .with_cell("\x1b[31m31\x1b[0m")
+                    .with_cell("a|b"),
+            )
+            .with_row(
+                Row::new()
+                    .with_cell(Cell::from("span").with_col_span(2))
+                    .with_cell("日本"),
+            );
+        let expected = vec![
+            vec![("name".into(), 1), ("age".into(), 1), ("x".into(), 1)],
+            vec![
+                ("Ann has a name that wraps".into(), 1),
+                ("31".into(), 1),
+                ("a|b".into(), 1),
+            ],
+            vec![("span".into(), 2), ("日本".into(), 1)],
+        ];
+        for style in [
+            TableStyle::SIMPLE,
+            TableStyle::EXTENDED,
+            TableStyle::THIN,
+            TableStyle::ROUNDED,
+            TableStyle::ELEGANT,
+            TableStyle::BLANK,
+        ] {
+            let text = table.clone().with_style(style).fixed_width(28).to_string();
+            assert_eq!(cells(&Table::parse(&text).unwrap()), expected);
+        }
+        let text = table.to_rst_grid().with_width(28).to_string();
+        assert_eq!(cells(&Table::parse(&text).unwrap()), expected);
+
+        let text = table.clone().with_style(TableStyle::EMPTY).to_string();
+        let err = Table::parse(&text).unwrap_err();
+        assert_eq!(err.to_string(), "line 1: unknown table border ` `");
+    }
+
+    #[test]
+    fn parse_rows_without_separators() {
+        let text = "+------+-----+
+| name | age |
++------+-----+
+| Ann  | 31  |
+| Bob  | 7   |
++------+-----+
+";
+        let table = TableParser::new()
+            .with_separate_rows(false)
+            .parse(text)
+            .unwrap();
+        assert_eq!(
+            cells(&table),
+            vec![
+                vec![("name".into(), 1), ("age".into(), 1)],
+                vec![("Ann".into(), 1), ("31".into(), 1)],
+                vec![("Bob".into(), 1), ("7".into(), 1)],
+            ]
+        );
+
+        let table = TableParser::new().with_header(false).parse(text).unwrap();
+        assert!(table.header().is_none());
+        assert_eq!(
+            cells(&table)[1],
+            vec![("Ann Bob".into(), 1), ("31 7".into(), 1)]
+        );
+    }
+}
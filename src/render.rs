@@ -0,0 +1,550 @@
+use crate::{
+    asciidoc::AsciiDoc, latex::Latex, rst::RstGrid, rst::RstSimple, svg::Svg, Cell, Table,
+};
+use std::{
+    collections::HashMap,
+    fmt,
+    ops::Range,
+    str::FromStr,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+/// The result of laying out a table: the width of each column, and where each cell's text is
+/// split into lines.
+///
+/// Created using `Table::compute_layout`, and passed to a `Renderer` when writing out a table.
+#[derive(Debug, Clone)]
+pub struct Layout<'a> {
+    pub(crate) column_widths: Vec<usize>,
+    pub(crate) row_lines: Vec<usize>,
+    pub(crate) cells: Vec<Vec<CellLayout<'a>>>,
+}
+
+impl<'a> Layout<'a> {
+    /// The width of each column, including padding but not borders.
+    pub fn column_widths(&self) -> &[usize] {
+        &self.column_widths
+    }
+
+    /// The number of lines needed to draw each row, not including separators.
+    ///
+    /// If the table has a header, it comes first, and if it has a footer, it comes last.
+    pub fn row_lines(&self) -> &[usize] {
+        &self.row_lines
+    }
+
+    /// The layout of every cell, grouped by row.
+    ///
+    /// If the table has a header, it comes first, and if it has a footer, it comes last.
+    pub fn cells(&self) -> &[Vec<CellLayout<'a>>] {
+        &self.cells
+    }
+}
+
+/// The result of laying out a single cell.
+#[derive(Debug, Clone)]
+pub struct CellLayout<'a> {
+    pub(crate) cell: &'a Cell<'a>,
+    pub(crate) width: usize,
+    /// The position of each line in the cell's content (without ANSI escapes).
+    pub(crate) lines: Lines,
+}
+
+impl<'a> CellLayout<'a> {
+    /// The cell that was laid out.
+    pub fn cell(&self) -> &'a Cell<'a> {
+        self.cell
+    }
+
+    /// The width of the cell, including padding and any borders it spans over.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// The text of each line, without ANSI escape sequences or trailing whitespace.
+    pub fn lines(&self) -> impl ExactSizeIterator<Item = &'a str> + '_ {
+        let content = self.cell.content_for_layout();
+        self.lines.iter().map(move |line| &content[line.clone()])
+    }
+
+    /// Write out a line of the cell, including padding.
+    ///
+    /// Lines past the end of the content are drawn empty.
+    pub(crate) fn render_line(&self, line_idx: usize, f: &mut dyn fmt::Write) -> fmt::Result {
+        let line = self.lines.get(line_idx).cloned().unwrap_or(0..0);
+        self.cell.render_line(line, self.width, f)
+    }
+}
+
+/// Where a cell's text is split into lines, as positions in its content without ANSI escapes.
+pub(crate) type Lines = Arc<[Range<usize>]>;
+
+/// Gets the lines for a cell, given its width.
+pub(crate) type CellLines<'a> = dyn Fn(&Cell, usize) -> Lines + Sync + 'a;
+
+/// Cached lines, keyed by the cell's revision and width.
+pub(crate) type LinesMap = HashMap<(u64, usize), Lines>;
+
+/// Remembers where the text in cells was wrapped, so that laying out a table again is cheap.
+///
+/// Pass the same cache each time you render a table (e.g. when redrawing a TUI) using
+/// `Rendered::with_cache` or `Table::compute_layout_cached`. Only cells that have changed, or
+/// that are now a different width, will be wrapped again.
+///
+/// The cache holds the cells from the most recent layout it was used for, so it doesn't grow if
+/// the table changes or is resized.
+#[derive(Debug, Default)]
+pub struct LayoutCache {
+    lines: Mutex<LinesMap>,
+}
+
+impl LayoutCache {
+    /// Create an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of cell layouts in the cache.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Whether the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Remove everything from the cache.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    /// Take the lines from the previous layout out of the cache.
+    pub(crate) fn take(&self) -> LinesMap {
+        std::mem::take(&mut *self.lock())
+    }
+
+    /// Replace the contents of the cache with the lines from the given layout.
+    pub(crate) fn store(&self, cells: &[Vec<CellLayout>]) {
+        let mut lines = self.lock();
+        lines.clear();
+        for cell in cells.iter().flatten() {
+            lines.insert((cell.cell.revision, cell.width), cell.lines.clone());
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, LinesMap> {
+        // The map is always valid, even if another thread panicked while using it.
+        self.lines.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Something that knows how to write out a table in some format.
+///
+/// Implement this to add your own output formats, and use it with `Table::render_with`.
+pub trait Renderer {
+    /// The width of the border between cells, used when laying out the table.
+    ///
+    /// Defaults to `1`.
+    fn border_width(&self, table: &Table) -> usize {
+        let _ = table;
+        1
+    }
+
+    /// Write out the table, given its layout.
+    fn render(&self, table: &Table, layout: &Layout, f: &mut dyn fmt::Write) -> fmt::Result;
+}
+
+impl<R: Renderer + ?Sized> Renderer for &R {
+    fn border_width(&self, table: &Table) -> usize {
+        (**self).border_width(table)
+    }
+
+    fn render(&self, table: &Table, layout: &Layout, f: &mut dyn fmt::Write) -> fmt::Result {
+        (**self).render(table, layout, f)
+    }
+}
+
+impl<R: Renderer + ?Sized> Renderer for Box<R> {
+    fn border_width(&self, table: &Table) -> usize {
+        (**self).border_width(table)
+    }
+
+    fn render(&self, table: &Table, layout: &Layout, f: &mut dyn fmt::Write) -> fmt::Result {
+        (**self).render(table, layout, f)
+    }
+}
+
+/// A table that will be written out using a `Renderer` when displayed.
+///
+/// Created using `Table::render_with`, or one of the methods for a specific format like
+/// `Table::to_latex`.
+#[derive(Debug, Clone, Copy)]
+pub struct Rendered<'a, R> {
+    pub(crate) table: &'a Table<'a>,
+    pub(crate) renderer: R,
+    pub(crate) width: Option<usize>,
+    pub(crate) cache: Option<&'a LayoutCache>,
+    /// The start and end of the rows to write, if not all of them.
+    pub(crate) rows: Option<(usize, usize)>,
+    pub(crate) row_position: bool,
+}
+
+impl<'a, R: Renderer> Rendered<'a, R> {
+    pub(crate) fn new(table: &'a Table<'a>, renderer: R) -> Self {
+        Self {
+            table,
+            renderer,
+            width: None,
+            cache: None,
+            rows: None,
+            row_position: false,
+        }
+    }
+
+    /// Wrap cell contents so the table fits in the given width.
+    ///
+    /// By default cells are as wide as their contents.
+    pub fn with_width(mut self, width: usize) -> Self {
+        self.set_width(width);
+        self
+    }
+
+    /// Wrap cell contents so the table fits in the given width.
+    ///
+    /// By default cells are as wide as their contents.
+    pub fn set_width(&mut self, width: usize) -> &mut Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// Reuse the layout of cells from previous renders using the given cache.
+    pub fn with_cache(mut self, cache: &'a LayoutCache) -> Self {
+        self.set_cache(cache);
+        self
+    }
+
+    /// Reuse the layout of cells from previous renders using the given cache.
+    pub fn set_cache(&mut self, cache: &'a LayoutCache) -> &mut Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Only write the given rows, along with the header and footer (if any).
+    ///
+    /// Columns are sized to fit the whole table, so they are the same width whichever rows are
+    /// written. Rows past the end of the table are ignored.
+    pub fn with_rows(mut self, rows: Range<usize>) -> Self {
+        self.set_rows(rows);
+        self
+    }
+
+    /// Only write the given rows, along with the header and footer (if any).
+    ///
+    /// Columns are sized to fit the whole table, so they are the same width whichever rows are
+    /// written. Rows past the end of the table are ignored.
+    pub fn set_rows(&mut self, rows: Range<usize>) -> &mut Self {
+        self.rows = Some((rows.start, rows.end));
+        self
+    }
+
+    /// Only write a page of rows, where pages are numbered from 0.
+    ///
+    /// See `with_rows` for details.
+    pub fn with_page(mut self, page: usize, page_size: usize) -> Self {
+        self.set_page(page, page_size);
+        self
+    }
+
+    /// Only write a page of rows, where pages are numbered from 0.
+    ///
+    /// See `with_rows` for details.
+    pub fn set_page(&mut self, page: usize, page_size: usize) -> &mut Self {
+        let start = page.saturating_mul(page_size);
+        self.set_rows(start..start.saturating_add(page_size))
+    }
+
+    /// Whether to write a line after the table saying which rows are shown, e.g.
+    /// `rows 51–100 of 2300`.
+    ///
+    /// Only used when writing some of the rows.
+    pub fn with_row_position(mut self, row_position: bool) -> Self {
+        self.set_row_position(row_position);
+        self
+    }
+
+    /// Whether to write a line after the table saying which rows are shown, e.g.
+    /// `rows 51–100 of 2300`.
+    ///
+    /// Only used when writing some of the rows.
+    pub fn set_row_position(&mut self, row_position: bool) -> &mut Self {
+        self.row_position = row_position;
+        self
+    }
+}
+
+impl<R: Renderer> fmt::Display for Rendered<'_, R> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let border_width = self.renderer.border_width(self.table);
+        let Some((start, end)) = self.rows else {
+            let layout = self.table.layout(self.width, border_width, self.cache);
+            return self.renderer.render(self.table, &layout, f);
+        };
+
+        let total = self.table.rows.len();
+        let rows = start.min(total)..end.clamp(start.min(total), total);
+        let column_widths = self.table.column_widths(self.width, border_width);
+        let page = self.table.slice_rows(rows.clone());
+        let layout = page.layout_with_widths(column_widths, border_width, self.cache);
+        self.renderer.render(&page, &layout, f)?;
+        if self.row_position {
+            if rows.is_empty() {
+                writeln!(f, "0 of {total} rows")?;
+            } else {
+                writeln!(f, "rows {}–{} of {total}", rows.start + 1, rows.end)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Draws the table using the box characters from its style.
+///
+/// This is how tables are displayed by default.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Text;
+
+impl Renderer for Text {
+    fn border_width(&self, table: &Table) -> usize {
+        table.style.border_width()
+    }
+
+    fn render(&self, table: &Table, layout: &Layout, f: &mut dyn fmt::Write) -> fmt::Result {
+        table.render_box(&table.box_style(), layout, f)
+    }
+}
+
+/// The built-in output formats.
+///
+/// Formats can be parsed from their names (e.g. from a `--format` command line flag) using
+/// `FromStr`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Format {
+    /// Box drawing characters, as shown in the terminal.
+    Text,
+    /// A LaTeX `tabular` environment.
+    Latex,
+    /// A LaTeX `tabular` environment using rules from the `booktabs` package.
+    Booktabs,
+    /// A reStructuredText grid table.
+    RstGrid,
+    /// A reStructuredText simple table.
+    RstSimple,
+    /// An AsciiDoc table.
+    AsciiDoc,
+    /// An SVG image.
+    Svg,
+}
+
+impl Format {
+    /// All the built-in formats.
+    pub const ALL: [Format; 7] = [
+        Format::Text,
+        Format::Latex,
+        Format::Booktabs,
+        Format::RstGrid,
+        Format::RstSimple,
+        Format::AsciiDoc,
+        Format::Svg,
+    ];
+
+    /// The name of this format, as accepted by `FromStr`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Format::Text => "text",
+            Format::Latex => "latex",
+            Format::Booktabs => "booktabs",
+            Format::RstGrid => "rst-grid",
+            Format::RstSimple => "rst-simple",
+            Format::AsciiDoc => "asciidoc",
+            Format::Svg => "svg",
+        }
+    }
+
+    /// The renderer for this format, using the default settings.
+    pub fn renderer(self) -> Box<dyn Renderer> {
+        match self {
+            Format::Text => Box::new(Text),
+            Format::Latex => Box::new(Latex::new()),
+            Format::Booktabs => Box::new(Latex::new().with_booktabs(true)),
+            Format::RstGrid => Box::new(RstGrid),
+            Format::RstSimple => Box::new(RstSimple),
+            Format::AsciiDoc => Box::new(AsciiDoc),
+            Format::Svg => Box::new(Svg::new()),
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Format {
+    type Err = UnknownFormat;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_ascii_lowercase().as_str() {
+            "text" | "box" => Format::Text,
+            "latex" | "tex" => Format::Latex,
+            "booktabs" => Format::Booktabs,
+            "rst-grid" | "rst" => Format::RstGrid,
+            "rst-simple" => Format::RstSimple,
+            "asciidoc" | "adoc" => Format::AsciiDoc,
+            "svg" => Format::Svg,
+            _ => return Err(UnknownFormat(s.to_string())),
+        })
+    }
+}
+
+/// The error returned when parsing an unknown `Format`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownFormat(String);
+
+impl fmt::Display for UnknownFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unknown table format `{}`, expected one of: ", self.0)?;
+        for (idx, format) in Format::ALL.iter().enumerate() {
+            if idx > 0 {
+                f.write_str(", ")?;
+            }
+            f.write_str(format.name())?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for UnknownFormat {}
+
+#[cfg(test)]
+mod test {
+    use super::{Format, Layout, LayoutCache, Renderer};
+    use crate::{Row, Table, TableStyle};
+    use pretty_assertions::assert_eq;
+    use std::{fmt, sync::Arc};
+
+    #[test]
+    fn format_names() {
+        for format in Format::ALL {
+            assert_eq!(format.name().parse::<Format>(), Ok(format));
+        }
+        assert_eq!("adoc".parse::<Format>(), Ok(Format::AsciiDoc));
+        let err = "html".parse::<Format>().unwrap_err();
+        assert!(err.to_string().starts_with("unknown table format `html`"));
+    }
+
+    #[test]
+    fn custom_renderer() {
+        struct Widths;
+
+        impl Renderer for Widths {
+            fn render(&self, _: &Table, layout: &Layout, f: &mut dyn fmt::Write) -> fmt::Result {
+                write!(f, "{:?} {:?}", layout.column_widths(), layout.row_lines())
+            }
+        }
+
+        let table = Table::new()
+            .with_header(Row::new().with_cell("a").with_cell("bb"))
+            .with_row(Row::new().with_cell("a\nb").with_cell("ccc"));
+        assert_eq!(table.render_with(Widths).to_string(), "[3, 5] [1, 2]");
+    }
+
+    #[test]
+    fn layout_cache() {
+        let mut table = Table::new()
+            .with_header(Row::new().with_cell("a").with_cell("bb"))
+            .with_row(Row::new().with_cell("some text").with_cell("more text"));
+        let cache = LayoutCache::new();
+
+        let first = table.compute_layout_cached(Some(20), &cache);
+        assert_eq!(cache.len(), 4);
+        let second = table.compute_layout_cached(Some(20), &cache);
+        assert!(Arc::ptr_eq(
+            &first.cells[1][1].lines,
+            &second.cells[1][1].lines
+        ));
+        let resized = table.compute_layout_cached(Some(30), &cache);
+        assert!(!Arc::ptr_eq(
+            &first.cells[1][1].lines,
+            &resized.cells[1][1].lines
+        ));
+        assert_eq!(cache.len(), 4);
+
+        table.add_row(Row::new().with_cell("x").with_cell("y"));
+        for width in [16, 20, 30] {
+            assert_eq!(
+                table.fixed_width(width).to_string(),
+                table
+                    .render_with(crate::Text)
+                    .with_width(width)
+                    .with_cache(&cache)
+                    .to_string()
+            );
+        }
+        assert_eq!(cache.len(), 6);
+    }
+
+    #[test]
+    fn pages() {
+        let mut table = Table::new()
+            .with_style(TableStyle::SIMPLE)
+            .with_separate_rows(false)
+            .with_header(Row::new().with_cell("n").with_cell("word"));
+        for (n, word) in ["one", "two", "three", "four", "five"].iter().enumerate() {
+            table.add_row(Row::new().with_cell((n + 1).to_string()).with_cell(*word));
+        }
+        assert_eq!(table.num_pages(2), 3);
+
+        let expected = "+---+-------+
+| n | word  |
++---+-------+
+| 3 | three |
+| 4 | four  |
++---+-------+
+rows 3–4 of 5
+";
+        let page = table.render_page(1, 2).with_row_position(true);
+        assert_eq!(expected, page.to_string());
+        assert_eq!(
+            table.render_rows(2..4).to_string(),
+            table.render_page(1, 2).to_string()
+        );
+
+        let expected = "+---+-------+
+| n | word  |
++---+-------+
+| 5 | five  |
++---+-------+
+";
+        assert_eq!(expected, table.render_page(2, 2).to_string());
+        assert!(table
+            .render_page(3, 2)
+            .with_row_position(true)
+            .to_string()
+            .ends_with("+---+-------+\n0 of 5 rows\n"));
+    }
+
+    #[test]
+    fn page_past_end_with_footer() {
+        let table = Table::new()
+            .with_style(TableStyle::SIMPLE)
+            .with_row(Row::new().with_cell("1").with_cell("one"))
+            .with_footer(Row::new().with_cell("Total").with_cell("1"));
+        let expected = "+-------+-----+
+| Total | 1   |
++-------+-----+
+";
+        assert_eq!(expected, table.render_page(3, 10).to_string());
+    }
+}
@@ -1,6 +1,9 @@
-use crate::{Cell, TableStyle};
+use crate::{
+    render::{CellLayout, CellLines},
+    Cell, TableStyle,
+};
 use itertools::Itertools;
-use std::fmt::{self, Write};
+use std::fmt;
 
 /// A set of table cells
 #[derive(Debug, Clone)]
@@ -8,6 +11,9 @@ pub struct Row<'data> {
     pub(crate) cells: Vec<Cell<'data>>,
     /// Whether the row should have a top border or not
     pub(crate) has_separator: bool,
+    /// Whether the row is always separated from the rows above and below it, even if the table
+    /// doesn't separate rows. Used for group headers and subtotals.
+    pub(crate) always_separated: bool,
 }
 
 impl<'data> Default for Row<'data> {
@@ -15,6 +21,7 @@ impl<'data> Default for Row<'data> {
         Self {
             cells: vec![],
             has_separator: true,
+            always_separated: false,
         }
     }
 }
@@ -36,6 +43,33 @@ impl<'data> Row<'data> {
         self
     }
 
+    /// Whether the row should have a top border or not
+    pub fn has_separator(&self) -> bool {
+        self.has_separator
+    }
+
+    pub fn cells(&self) -> &[Cell<'data>] {
+        &self.cells
+    }
+
+    /// Each cell, along with the first column it covers.
+    pub(crate) fn cells_with_columns(&self) -> impl Iterator<Item = (usize, &Cell<'data>)> {
+        self.cells.iter().scan(0, |column, cell| {
+            let start = *column;
+            *column += cell.col_span;
+            Some((start, cell))
+        })
+    }
+
+    /// The cell covering the given column, taking into account cells that span several columns.
+    pub(crate) fn cell_at_column(&self, column: usize) -> Option<&Cell<'data>> {
+        let mut start = 0;
+        self.cells.iter().find(|cell| {
+            start += cell.col_span;
+            column < start
+        })
+    }
+
     pub fn add_cell(&mut self, cell: impl Into<Cell<'data>>) -> &mut Self {
         self.cells.push(cell.into());
         self
@@ -53,30 +87,35 @@ impl<'data> Row<'data> {
 
     /// Ask the row to calculate its layout.
     ///
-    /// Returns the number of lines required to display this row (without the top border).
-    pub(crate) fn layout(&self, column_widths: &[usize], border_width: usize) -> usize {
-        let mut max_lines = 0;
-        let mut idx = 0;
-        for cell in self.cells.iter() {
-            // start with the extra space for borders
-            let mut width = (cell.col_span + 1) * border_width;
-
-            // add in space for cell content.
-            for w in column_widths[idx..idx + cell.col_span].iter().copied() {
-                width += w;
-            }
-            let num_lines = cell.layout(Some(width));
-            idx += cell.col_span;
-            max_lines = max_lines.max(num_lines);
-        }
-        max_lines
+    /// `cell_lines` is used to get where each cell is split into lines, given its width.
+    ///
+    /// Returns the layout of each cell.
+    pub(crate) fn layout(
+        &self,
+        column_widths: &[usize],
+        border_width: usize,
+        cell_lines: &CellLines<'_>,
+    ) -> Vec<CellLayout<'_>> {
+        let mut widths = column_widths;
+        self.cells
+            .iter()
+            .map(|cell| {
+                let width;
+                (width, widths) = cell.width(border_width, widths);
+                CellLayout {
+                    cell,
+                    width,
+                    lines: cell_lines(cell, width),
+                }
+            })
+            .collect()
     }
 
     pub fn render_top_separator(
         &self,
         cell_widths: &[usize],
         style: &TableStyle,
-        f: &mut fmt::Formatter,
+        f: &mut dyn fmt::Write,
     ) -> fmt::Result {
         if !self.has_separator {
             // don't draw anything
@@ -108,7 +147,7 @@ impl<'data> Row<'data> {
         &self,
         cell_widths: &[usize],
         style: &TableStyle,
-        f: &mut fmt::Formatter,
+        f: &mut dyn fmt::Write,
     ) -> fmt::Result {
         if !self.has_separator {
             // don't draw anything
@@ -141,12 +180,23 @@ impl<'data> Row<'data> {
         prev: &Row,
         cell_widths: &[usize],
         style: &TableStyle,
-        f: &mut fmt::Formatter,
+        f: &mut dyn fmt::Write,
     ) -> fmt::Result {
         if !self.has_separator {
             // don't draw anything
             return Ok(());
         }
+        self.write_separator(prev, cell_widths, style, f)
+    }
+
+    /// Like `render_separator`, but the separator is drawn even if `has_separator` is `false`.
+    pub(crate) fn write_separator(
+        &self,
+        prev: &Row,
+        cell_widths: &[usize],
+        style: &TableStyle,
+        f: &mut dyn fmt::Write,
+    ) -> fmt::Result {
         f.write_char(style.outer_left_vertical)?;
         let mut iter = cell_widths
             .iter()
@@ -164,19 +214,15 @@ impl<'data> Row<'data> {
 
     /// Formats a row based on the provided table style
     pub(crate) fn render_content(
-        &self,
-        column_widths: &[usize],
+        cells: &[CellLayout],
         num_lines: usize,
         style: &TableStyle,
-        f: &mut fmt::Formatter,
+        f: &mut dyn fmt::Write,
     ) -> fmt::Result {
         for line_num in 0..num_lines {
-            let mut width;
-            let mut widths = column_widths;
-            for cell in &self.cells {
+            for cell in cells {
                 f.write_char(style.vertical)?;
-                (width, widths) = cell.width(style.border_width(), widths);
-                cell.render_line(line_num, width, f)?;
+                cell.render_line(line_num, f)?;
             }
             f.write_char(style.vertical)?;
             writeln!(f)?;
@@ -201,7 +247,7 @@ impl<'data> Row<'data> {
         impl<'a> Iterator for IterJoins<'a> {
             type Item = BorderTy;
             fn next(&mut self) -> Option<Self::Item> {
-                let out = Some(match &mut self.cols_remaining {
+                Some(match &mut self.cols_remaining {
                     // we are past the end
                     0 => BorderTy::Empty,
                     // we are at the end of a cell
@@ -214,8 +260,7 @@ impl<'data> Row<'data> {
                         *n -= 1;
                         BorderTy::Middle
                     }
-                });
-                out
+                })
             }
         }
         IterJoins {
@@ -297,9 +342,10 @@ impl Borders {
 /// Note that the tuple implementations of these methods always copy strings.
 pub trait IntoRow {
     /// Returns a set of cells that can be used as headers for the cells of data of this type.
-    fn headers(&self) -> Row;
+    fn headers(&self) -> Row<'_>;
     /// Returns the row.
-    fn into_row(&self) -> Row;
+    #[allow(clippy::wrong_self_convention)]
+    fn into_row(&self) -> Row<'_>;
 }
 
 macro_rules! impl_row_for_tuple {
@@ -312,7 +358,7 @@ macro_rules! impl_row_for_tuple {
                       $rest_ty: ::std::fmt::Display,
                   )*
         {
-            fn headers(&self) -> Row {
+            fn headers(&self) -> Row<'_> {
                 let mut row = Row::default();
                 row.add_cell(stringify!($first_ty));
                 $(
@@ -321,7 +367,7 @@ macro_rules! impl_row_for_tuple {
                 row
             }
 
-            fn into_row(&self) -> Row {
+            fn into_row(&self) -> Row<'_> {
                 #[allow(non_snake_case)]
                 let (
                     ref $first_ty,
@@ -0,0 +1,229 @@
+use crate::{Alignment, BoxStyle, Layout, Rendered, Renderer, Row, Table, TableStyle};
+use std::fmt::{self, Write};
+use unicode_width::UnicodeWidthStr;
+
+/// Formats a table as a reStructuredText grid table.
+///
+/// Every row is separated from the next, since this is how grid tables tell rows apart, and the
+/// header (if any) is separated from the body using `=`. reStructuredText has no footers, so a
+/// footer is written as the last row of the body.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RstGrid;
+
+impl Renderer for RstGrid {
+    fn render(&self, table: &Table, layout: &Layout, f: &mut dyn fmt::Write) -> fmt::Result {
+        let box_style = BoxStyle {
+            style: TableStyle::SIMPLE,
+            header_style: TableStyle {
+                horizontal: '=',
+                ..TableStyle::SIMPLE
+            },
+            footer_style: TableStyle::SIMPLE,
+            has_separate_rows: true,
+            has_top_border: true,
+            has_bottom_border: true,
+        };
+        table.render_box(&box_style, layout, f)
+    }
+}
+
+/// Formats a table as a reStructuredText simple table.
+///
+/// Simple tables cannot wrap text, so each row is written on a single line, and any newlines in
+/// cells are replaced with spaces. Cells spanning multiple columns are underlined with `-`. A
+/// footer is written as the last row of the body.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RstSimple;
+
+impl Renderer for RstSimple {
+    fn render(&self, table: &Table, _: &Layout, f: &mut dyn fmt::Write) -> fmt::Result {
+        let header = table.header.as_ref().map(simple_row);
+        let rows: Vec<_> = table
+            .rows
+            .iter()
+            .chain(&table.footer)
+            .map(simple_row)
+            .collect();
+        let widths = simple_column_widths(header.iter().chain(rows.iter()), table.num_columns());
+
+        write_simple_border('=', &widths, f)?;
+        if let Some(header) = &header {
+            write_simple_row(header, &widths, f)?;
+            write_simple_border('=', &widths, f)?;
+        }
+        for row in rows.iter() {
+            write_simple_row(row, &widths, f)?;
+        }
+        write_simple_border('=', &widths, f)
+    }
+}
+
+/// A cell of a simple table, with its text on a single line.
+struct SimpleCell {
+    text: String,
+    col_span: usize,
+    alignment: Alignment,
+}
+
+fn simple_row(row: &Row) -> Vec<SimpleCell> {
+    row.cells
+        .iter()
+        .enumerate()
+        .map(|(idx, cell)| {
+            let mut text = cell.content_for_layout().replace(['\r', '\n'], " ");
+            // A blank first column would make this a continuation of the previous row, so use
+            // an empty comment instead.
+            if idx == 0 && text.trim().is_empty() {
+                text = "..".into();
+            }
+            SimpleCell {
+                text,
+                col_span: cell.col_span,
+                alignment: cell.alignment,
+            }
+        })
+        .collect()
+}
+
+/// Find the width of each column, making sure cells spanning multiple columns fit.
+fn simple_column_widths<'a>(
+    rows: impl Iterator<Item = &'a Vec<SimpleCell>> + Clone,
+    num_columns: usize,
+) -> Vec<usize> {
+    let mut widths = vec![1; num_columns];
+    for row in rows.clone() {
+        let mut idx = 0;
+        for cell in row.iter() {
+            if cell.col_span == 1 {
+                widths[idx] = widths[idx].max(cell.text.width());
+            }
+            idx += cell.col_span;
+        }
+    }
+    for row in rows {
+        let mut idx = 0;
+        for cell in row.iter() {
+            let available = simple_span_width(&widths[idx..idx + cell.col_span]);
+            let required = cell.text.width();
+            if required > available {
+                // give the extra space to the last column in the span.
+                widths[idx + cell.col_span - 1] += required - available;
+            }
+            idx += cell.col_span;
+        }
+    }
+    widths
+}
+
+/// The width of a set of columns, including the gaps between them.
+fn simple_span_width(widths: &[usize]) -> usize {
+    widths.iter().sum::<usize>() + 2 * widths.len().saturating_sub(1)
+}
+
+fn write_simple_border(ch: char, widths: &[usize], f: &mut dyn fmt::Write) -> fmt::Result {
+    let mut line = String::new();
+    for (idx, width) in widths.iter().enumerate() {
+        if idx > 0 {
+            line += "  ";
+        }
+        line.extend(std::iter::repeat_n(ch, *width));
+    }
+    writeln!(f, "{line}")
+}
+
+fn write_simple_row(row: &[SimpleCell], widths: &[usize], f: &mut dyn fmt::Write) -> fmt::Result {
+    let mut line = String::new();
+    let mut underline = String::new();
+    let mut idx = 0;
+    for cell in row.iter() {
+        if idx > 0 {
+            line += "  ";
+            underline += "  ";
+        }
+        let width = simple_span_width(&widths[idx..idx + cell.col_span]);
+        let gap = width - cell.text.width();
+        let (front, back) = match cell.alignment {
+            Alignment::Left => (0, gap),
+            Alignment::Center => (gap / 2, gap - gap / 2),
+            Alignment::Right => (gap, 0),
+        };
+        write!(line, "{:front$}{}{:back$}", "", cell.text, "")?;
+        underline.extend(std::iter::repeat_n('-', width));
+        idx += cell.col_span;
+    }
+    writeln!(f, "{}", line.trim_end())?;
+    if row.iter().any(|cell| cell.col_span > 1) {
+        writeln!(f, "{underline}")?;
+    }
+    Ok(())
+}
+
+impl<'data> Table<'data> {
+    /// Format the table as a reStructuredText grid table.
+    pub fn to_rst_grid(&self) -> Rendered<'_, RstGrid> {
+        self.render_with(RstGrid)
+    }
+
+    /// Format the table as a reStructuredText simple table.
+    pub fn to_rst_simple(&self) -> Rendered<'_, RstSimple> {
+        self.render_with(RstSimple)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Alignment, Cell, Row, Table, TableStyle};
+    use pretty_assertions::assert_eq;
+
+    fn test_table() -> Table<'static> {
+        Table::new()
+            .with_style(TableStyle::THIN)
+            .with_separate_rows(false)
+            .with_header(
+                Row::new()
+                    .with_cell(Cell::from("Inputs").with_col_span(2))
+                    .with_cell("Output"),
+            )
+            .with_row(
+                Row::new()
+                    .with_cell("False")
+                    .with_cell("False")
+                    .with_cell(Cell::from("False").with_alignment(Alignment::Right)),
+            )
+            .with_row(
+                Row::new()
+                    .with_cell("")
+                    .with_cell(Cell::from("A long cell that will wrap").with_col_span(2)),
+            )
+    }
+
+    #[test]
+    fn grid() {
+        let expected = r"+-----------------+---------+
+| Inputs          | Output  |
++=======+=========+=========+
+| False | False   |   False |
++-------+---------+---------+
+|       | A long cell that  |
+|       | will wrap         |
++-------+-------------------+
+";
+        let table = test_table();
+        assert_eq!(expected, table.to_rst_grid().with_width(32).to_string());
+    }
+
+    #[test]
+    fn simple() {
+        let expected = r"=====  =====  ===================
+Inputs        Output
+------------  -------------------
+=====  =====  ===================
+False  False                False
+..     A long cell that will wrap
+-----  --------------------------
+=====  =====  ===================
+";
+        let table = test_table();
+        assert_eq!(expected, table.to_rst_simple().to_string());
+    }
+}
@@ -0,0 +1,254 @@
+use crate::{Cell, Row, Table};
+
+impl<'data> Table<'data> {
+    /// Only keep the rows for which `keep` returns `true`.
+    ///
+    /// The header and footer are always kept.
+    pub fn filter(&mut self, mut keep: impl FnMut(&Row<'data>) -> bool) -> &mut Self {
+        self.rows.retain(|row| keep(row));
+        self
+    }
+
+    /// Only keep the given columns, in the given order.
+    ///
+    /// This can also be used to reorder columns, e.g. `select_columns(&[2, 0, 1])`. Cells that
+    /// span several columns will span however many of their columns are kept. If the kept columns
+    /// of a cell are no longer next to each other, the cell is repeated for each group. Columns
+    /// selected more than once or out of order get their own copy of the cell.
+    pub fn select_columns(&mut self, columns: &[usize]) -> &mut Self {
+        let rows = self
+            .header
+            .iter_mut()
+            .chain(&mut self.rows)
+            .chain(&mut self.footer);
+        for row in rows {
+            *row = select_row_columns(row, columns);
+        }
+        self
+    }
+
+    /// Remove the given columns.
+    ///
+    /// Cells that span several columns will span however many of their columns are left, and are
+    /// removed if none are left.
+    pub fn drop_columns(&mut self, columns: &[usize]) -> &mut Self {
+        let keep: Vec<_> = (0..self.num_columns())
+            .filter(|column| !columns.contains(column))
+            .collect();
+        self.select_columns(&keep)
+    }
+}
+
+/// Build a new row out of the cells covering the given columns.
+fn select_row_columns<'data>(row: &Row<'data>, columns: &[usize]) -> Row<'data> {
+    // The index of the cell covering each column.
+    let cell_idxs: Vec<usize> = row
+        .cells
+        .iter()
+        .enumerate()
+        .flat_map(|(idx, cell)| std::iter::repeat_n(idx, cell.col_span))
+        .collect();
+    let mut selected: Vec<(usize, Option<usize>)> = columns
+        .iter()
+        .map(|column| (*column, cell_idxs.get(*column).copied()))
+        .collect();
+    // Rows can be shorter than the table, so we don't need to fill in missing cells at the end.
+    while let Some((_, None)) = selected.last() {
+        selected.pop();
+    }
+
+    let mut cells: Vec<Cell<'data>> = vec![];
+    let mut prev: Option<(usize, usize)> = None;
+    for (column, cell_idx) in selected {
+        match cell_idx {
+            // The next column of the cell we just added, skipping any columns that aren't kept.
+            // Repeated or reordered columns get their own copy of the cell.
+            Some(idx)
+                if prev.is_some_and(|(prev_column, prev_idx)| {
+                    prev_idx == idx
+                        && prev_column < column
+                        && !columns
+                            .iter()
+                            .any(|c| (prev_column + 1..column).contains(c))
+                }) =>
+            {
+                let cell = cells.last_mut().unwrap();
+                cell.set_col_span(cell.col_span + 1);
+            }
+            Some(idx) => cells.push(row.cells[idx].clone().with_col_span(1)),
+            None => cells.push(Cell::from("")),
+        }
+        prev = cell_idx.map(|idx| (column, idx));
+    }
+    Row {
+        cells,
+        has_separator: row.has_separator,
+        always_separated: row.always_separated,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Cell, Row, Table, TableStyle};
+    use pretty_assertions::assert_eq;
+
+    fn people() -> Table<'static> {
+        Table::new()
+            .with_style(TableStyle::SIMPLE)
+            .with_separate_rows(false)
+            .with_header(
+                Row::new()
+                    .with_cell("Name")
+                    .with_cell("Age")
+                    .with_cell("City"),
+            )
+            .with_row(
+                Row::new()
+                    .with_cell("Ann")
+                    .with_cell("31")
+                    .with_cell("Oslo"),
+            )
+            .with_row(
+                Row::new()
+                    .with_cell(Cell::from("Bob (unknown age)").with_col_span(2))
+                    .with_cell("Rome"),
+            )
+            .with_row(Row::new().with_cell("Cy").with_cell("7"))
+    }
+
+    #[test]
+    fn filter() {
+        let mut table = people();
+        table.filter(|row| row.num_columns() == 3);
+        let expected = "+----------+--------+------+
+| Name     | Age    | City |
++----------+--------+------+
+| Ann      | 31     | Oslo |
+| Bob (unknown age) | Rome |
++-------------------+------+
+";
+        assert_eq!(expected, table.to_string());
+    }
+
+    #[test]
+    fn select_columns() {
+        let mut table = people();
+        table.select_columns(&[2, 0]);
+        let expected = "+------+-------------------+
+| City | Name              |
++------+-------------------+
+| Oslo | Ann               |
+| Rome | Bob (unknown age) |
+|      | Cy                |
++------+-------------------+
+";
+        assert_eq!(expected, table.to_string());
+
+        let mut table = people();
+        table.select_columns(&[1, 2, 0]);
+        let expected = "+-------------------+------+-------------------+
+| Age               | City | Name              |
++-------------------+------+-------------------+
+| 31                | Oslo | Ann               |
+| Bob (unknown age) | Rome | Bob (unknown age) |
+| 7                 |      | Cy                |
++-------------------+------+-------------------+
+";
+        assert_eq!(expected, table.to_string());
+    }
+
+    /// The text and span of each cell in the body.
+    fn spans<'a>(table: &'a Table) -> Vec<Vec<(&'a str, usize)>> {
+        table
+            .rows()
+            .iter()
+            .map(|row| {
+                row.cells()
+                    .iter()
+                    .map(|cell| (cell.content(), cell.col_span()))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// A table with a cell spanning all three columns.
+    fn wide() -> Table<'static> {
+        Table::new()
+            .with_row(Row::new().with_cell("a").with_cell("b").with_cell("c"))
+            .with_row(Row::new().with_cell(Cell::from("abc").with_col_span(3)))
+    }
+
+    #[test]
+    fn select_duplicate_columns() {
+        let mut table = wide();
+        table.select_columns(&[0, 0]);
+        assert_eq!(
+            spans(&table),
+            vec![vec![("a", 1), ("a", 1)], vec![("abc", 1), ("abc", 1)]]
+        );
+    }
+
+    #[test]
+    fn select_reordered_spanning_columns() {
+        let mut table = wide();
+        table.select_columns(&[1, 0]);
+        assert_eq!(
+            spans(&table),
+            vec![vec![("b", 1), ("a", 1)], vec![("abc", 1), ("abc", 1)]]
+        );
+
+        // Column 1 is kept, but not between 0 and 2, so the cell is split around it.
+        let mut table = wide();
+        table.select_columns(&[0, 2, 1]);
+        assert_eq!(
+            spans(&table),
+            vec![
+                vec![("a", 1), ("c", 1), ("b", 1)],
+                vec![("abc", 1), ("abc", 1), ("abc", 1)],
+            ]
+        );
+
+        let mut table = wide();
+        table.select_columns(&[1, 2, 0]);
+        assert_eq!(
+            spans(&table),
+            vec![
+                vec![("b", 1), ("c", 1), ("a", 1)],
+                vec![("abc", 2), ("abc", 1)]
+            ]
+        );
+    }
+
+    #[test]
+    fn select_across_removed_columns() {
+        let mut table = wide();
+        table.select_columns(&[0, 2]);
+        assert_eq!(
+            spans(&table),
+            vec![vec![("a", 1), ("c", 1)], vec![("abc", 2)]]
+        );
+    }
+
+    #[test]
+    fn drop_columns() {
+        let mut table = people();
+        table.drop_columns(&[2]);
+        assert_eq!(
+            spans(&table),
+            vec![
+                vec![("Ann", 1), ("31", 1)],
+                vec![("Bob (unknown age)", 2)],
+                vec![("Cy", 1), ("7", 1)],
+            ]
+        );
+        table.drop_columns(&[0, 1]);
+        assert!(table.rows().iter().all(|row| row.cells().is_empty()));
+
+        let mut table = wide();
+        table.drop_columns(&[1]);
+        assert_eq!(
+            spans(&table),
+            vec![vec![("a", 1), ("c", 1)], vec![("abc", 2)]]
+        );
+    }
+}
@@ -1,7 +1,10 @@
-use crate::{Cell, Row};
+use crate::{Alignment, Cell, Row, Table};
 use anyhow::anyhow;
 use serde::{ser, Serialize};
-use std::fmt;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+};
 
 type Result<T, E = Error> = std::result::Result<T, E>;
 pub struct Error(anyhow::Error);
@@ -39,91 +42,629 @@ impl serde::ser::Error for Error {
     }
 }
 
-pub fn serialize_row(value: impl Serialize) -> Result<Row<'static>> {
-    let mut serializer = Serializer {
-        row: Row::new(),
-        level: 0,
+/// How to write sequences (like `Vec`s) that are fields of a record.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum SequenceFormat {
+    /// Write the sequence as JSON.
+    #[default]
+    Json,
+    /// Write each item as text, joined by the given separator. Items that aren't strings or
+    /// numbers are written as JSON.
+    Join(String),
+    /// Put each item on its own row, repeating the other fields of the record. Items that are
+    /// structs or maps are flattened into columns if they are within the flatten depth.
+    Explode,
+}
+
+/// Settings for building a table from serializable records, used with `Table::from_serde_with`.
+#[derive(Debug, Clone)]
+pub struct SerdeOptions {
+    header: bool,
+    keyed: bool,
+    missing: String,
+    flatten_depth: usize,
+    sequences: SequenceFormat,
+    align_numbers: bool,
+    precision: Option<usize>,
+    thousands_separator: Option<char>,
+    none: String,
+    true_text: String,
+    false_text: String,
+}
+
+impl Default for SerdeOptions {
+    fn default() -> Self {
+        Self {
+            header: true,
+            keyed: false,
+            missing: String::new(),
+            flatten_depth: 0,
+            sequences: SequenceFormat::Json,
+            align_numbers: true,
+            precision: None,
+            thousands_separator: None,
+            none: String::new(),
+            true_text: "true".into(),
+            false_text: "false".into(),
+        }
+    }
+}
+
+impl SerdeOptions {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn header(&self) -> bool {
+        self.header
+    }
+
+    /// Whether to add a header row using the field names (or map keys) of the first record.
+    ///
+    /// Defaults to `true`. Records without field names, like tuples, never get a header.
+    pub fn with_header(mut self, header: bool) -> Self {
+        self.set_header(header);
+        self
+    }
+
+    /// Whether to add a header row using the field names (or map keys) of the first record.
+    ///
+    /// Defaults to `true`. Records without field names, like tuples, never get a header.
+    pub fn set_header(&mut self, header: bool) -> &mut Self {
+        self.header = header;
+        self
+    }
+
+    pub fn keyed(&self) -> bool {
+        self.keyed
+    }
+
+    /// Whether to line up cells by their field name (or map key), rather than their position.
+    ///
+    /// There is a column for every key in any record, in the order they are first seen, so
+    /// records can have different fields. Fields without a name use their position as a key.
+    /// Defaults to `false`.
+    pub fn with_keyed(mut self, keyed: bool) -> Self {
+        self.set_keyed(keyed);
+        self
+    }
+
+    /// Whether to line up cells by their field name (or map key), rather than their position.
+    ///
+    /// There is a column for every key in any record, in the order they are first seen, so
+    /// records can have different fields. Fields without a name use their position as a key.
+    /// Defaults to `false`.
+    pub fn set_keyed(&mut self, keyed: bool) -> &mut Self {
+        self.keyed = keyed;
+        self
+    }
+
+    pub fn missing(&self) -> &str {
+        &self.missing
+    }
+
+    /// The text for cells of fields that a record doesn't have, in keyed mode.
+    ///
+    /// Defaults to an empty string.
+    pub fn with_missing(mut self, missing: impl Into<String>) -> Self {
+        self.set_missing(missing);
+        self
+    }
+
+    /// The text for cells of fields that a record doesn't have, in keyed mode.
+    ///
+    /// Defaults to an empty string.
+    pub fn set_missing(&mut self, missing: impl Into<String>) -> &mut Self {
+        self.missing = missing.into();
+        self
+    }
+
+    pub fn flatten_depth(&self) -> usize {
+        self.flatten_depth
+    }
+
+    /// How many levels of nested structs and maps to split into their own columns, named
+    /// `parent.child`.
+    ///
+    /// Defaults to 0, so nested values are written as JSON.
+    pub fn with_flatten_depth(mut self, flatten_depth: usize) -> Self {
+        self.set_flatten_depth(flatten_depth);
+        self
+    }
+
+    /// How many levels of nested structs and maps to split into their own columns, named
+    /// `parent.child`.
+    ///
+    /// Defaults to 0, so nested values are written as JSON.
+    pub fn set_flatten_depth(&mut self, flatten_depth: usize) -> &mut Self {
+        self.flatten_depth = flatten_depth;
+        self
+    }
+
+    pub fn sequences(&self) -> &SequenceFormat {
+        &self.sequences
+    }
+
+    /// How to write sequences that are fields of a record.
+    ///
+    /// Defaults to `SequenceFormat::Json`.
+    pub fn with_sequences(mut self, sequences: SequenceFormat) -> Self {
+        self.set_sequences(sequences);
+        self
+    }
+
+    /// How to write sequences that are fields of a record.
+    ///
+    /// Defaults to `SequenceFormat::Json`.
+    pub fn set_sequences(&mut self, sequences: SequenceFormat) -> &mut Self {
+        self.sequences = sequences;
+        self
+    }
+
+    pub fn align_numbers(&self) -> bool {
+        self.align_numbers
+    }
+
+    /// Whether to right align cells containing numbers.
+    ///
+    /// Defaults to `true`.
+    pub fn with_align_numbers(mut self, align_numbers: bool) -> Self {
+        self.set_align_numbers(align_numbers);
+        self
+    }
+
+    /// Whether to right align cells containing numbers.
+    ///
+    /// Defaults to `true`.
+    pub fn set_align_numbers(&mut self, align_numbers: bool) -> &mut Self {
+        self.align_numbers = align_numbers;
+        self
+    }
+
+    pub fn precision(&self) -> Option<usize> {
+        self.precision
+    }
+
+    /// Write floating point numbers with the given number of decimal places.
+    ///
+    /// By default they are written with as many as needed.
+    pub fn with_precision(mut self, precision: usize) -> Self {
+        self.set_precision(precision);
+        self
+    }
+
+    /// Write floating point numbers with the given number of decimal places.
+    ///
+    /// By default they are written with as many as needed.
+    pub fn set_precision(&mut self, precision: usize) -> &mut Self {
+        self.precision = Some(precision);
+        self
+    }
+
+    pub fn thousands_separator(&self) -> Option<char> {
+        self.thousands_separator
+    }
+
+    /// Separate groups of thousands in numbers with the given character, e.g. `1,000,000`.
+    ///
+    /// By default numbers are not separated.
+    pub fn with_thousands_separator(mut self, separator: char) -> Self {
+        self.set_thousands_separator(separator);
+        self
+    }
+
+    /// Separate groups of thousands in numbers with the given character, e.g. `1,000,000`.
+    ///
+    /// By default numbers are not separated.
+    pub fn set_thousands_separator(&mut self, separator: char) -> &mut Self {
+        self.thousands_separator = Some(separator);
+        self
+    }
+
+    pub fn none(&self) -> &str {
+        &self.none
+    }
+
+    /// The text for `None` values, e.g. a dimmed `"\x1b[2m—\x1b[0m"`.
+    ///
+    /// Defaults to an empty string.
+    pub fn with_none(mut self, none: impl Into<String>) -> Self {
+        self.set_none(none);
+        self
+    }
+
+    /// The text for `None` values, e.g. a dimmed `"\x1b[2m—\x1b[0m"`.
+    ///
+    /// Defaults to an empty string.
+    pub fn set_none(&mut self, none: impl Into<String>) -> &mut Self {
+        self.none = none.into();
+        self
+    }
+
+    /// The text for `true` and `false` values.
+    pub fn bools(&self) -> (&str, &str) {
+        (&self.true_text, &self.false_text)
+    }
+
+    /// The text for `true` and `false` values, e.g. `"✓"` and `"✗"`.
+    ///
+    /// Defaults to `"true"` and `"false"`.
+    pub fn with_bools(
+        mut self,
+        true_text: impl Into<String>,
+        false_text: impl Into<String>,
+    ) -> Self {
+        self.set_bools(true_text, false_text);
+        self
+    }
+
+    /// The text for `true` and `false` values, e.g. `"✓"` and `"✗"`.
+    ///
+    /// Defaults to `"true"` and `"false"`.
+    pub fn set_bools(
+        &mut self,
+        true_text: impl Into<String>,
+        false_text: impl Into<String>,
+    ) -> &mut Self {
+        self.true_text = true_text.into();
+        self.false_text = false_text.into();
+        self
+    }
+}
+
+impl<'data> Table<'data> {
+    /// Build a table with a row for each record, and a header row from the field names of the
+    /// first record.
+    ///
+    /// Nested values are written as JSON, and all cells are left aligned. Use `from_serde_with` to
+    /// right align numbers and change how values are written.
+    pub fn from_serde(data: impl IntoIterator<Item = impl Serialize>) -> anyhow::Result<Self> {
+        Self::from_serde_with(data, &SerdeOptions::default().with_align_numbers(false))
+    }
+
+    /// Build a table with a row for each record, using the given options.
+    pub fn from_serde_with(
+        data: impl IntoIterator<Item = impl Serialize>,
+        options: &SerdeOptions,
+    ) -> anyhow::Result<Self> {
+        let mut records = vec![];
+        for record in data {
+            records.extend(serialize_rows(record, options)?);
+        }
+        // Records without field names, like tuples, don't get a header.
+        let has_keys = records.iter().flatten().any(|field| !field.path.is_empty());
+        let (keys, rows) = if options.keyed {
+            keyed_rows(records, &options.missing)
+        } else {
+            positional_rows(records)
+        };
+        let mut table = Table::from_rows(rows);
+        if options.header && has_keys {
+            table.set_header(new_row(keys.into_iter().map(Cell::from)));
+        }
+        Ok(table)
+    }
+}
+
+impl Table<'static> {
+    /// Build a table showing a single record, with a row for each field containing its name and
+    /// value.
+    ///
+    /// Nested structs and maps are shown as sections: a row with their name, separated from the
+    /// rows around it, followed by their fields, indented. This is easier to read than `from_serde`
+    /// for records with many fields.
+    pub fn from_serde_record(record: impl Serialize) -> anyhow::Result<Self> {
+        Self::from_serde_record_with(record, &SerdeOptions::default())
+    }
+
+    /// Build a table showing a single record, using the given options.
+    ///
+    /// `flatten_depth` and `keyed` are ignored, and `SequenceFormat::Explode` is treated like
+    /// `SequenceFormat::Json`.
+    pub fn from_serde_record_with(
+        record: impl Serialize,
+        options: &SerdeOptions,
+    ) -> anyhow::Result<Self> {
+        let mut options = options.clone();
+        options.flatten_depth = usize::MAX;
+        if options.sequences == SequenceFormat::Explode {
+            options.sequences = SequenceFormat::Json;
+        }
+        let fields = serialize_rows(record, &options)?.into_iter().next();
+
+        let mut table = Table::new().with_separate_rows(false);
+        if options.header {
+            table.set_header(Row::new().with_cell("field").with_cell("value"));
+        }
+        let mut section: Vec<String> = vec![];
+        for (idx, Field { path, cell }) in fields.into_iter().flatten().enumerate() {
+            let (name, parents) = match path.split_last() {
+                Some((name, parents)) => (name.clone(), parents),
+                None => (idx.to_string(), &[][..]),
+            };
+            // Start the sections this field is in that the previous field wasn't.
+            let same = section
+                .iter()
+                .zip(parents)
+                .take_while(|(a, b)| a == b)
+                .count();
+            for (depth, name) in parents.iter().enumerate().skip(same) {
+                let cell = Cell::from(indent(depth, name)).with_col_span(2);
+                let mut row = Row::new().with_cell(cell);
+                row.always_separated = true;
+                table.add_row(row);
+            }
+            section = parents.to_vec();
+            table.add_row(
+                Row::new()
+                    .with_cell(indent(parents.len(), &name))
+                    .with_cell(cell),
+            );
+        }
+        Ok(table)
+    }
+}
+
+/// Indent the name of a field in a section.
+fn indent(depth: usize, name: &str) -> String {
+    format!("{:width$}{name}", "", width = depth * 2)
+}
+
+/// Use the fields of each record in order, with the keys of the first record.
+fn positional_rows(records: Vec<Vec<Field>>) -> (Vec<String>, Vec<Row<'static>>) {
+    let keys = match records.first() {
+        Some(fields) => fields
+            .iter()
+            .map(|field| field.key().unwrap_or_default())
+            .collect(),
+        None => vec![],
     };
+    let rows = records
+        .into_iter()
+        .map(|fields| new_row(fields.into_iter().map(|field| field.cell)))
+        .collect();
+    (keys, rows)
+}
+
+/// Line up the fields of each record by key, with a column for every key in any record.
+fn keyed_rows(records: Vec<Vec<Field>>, missing: &str) -> (Vec<String>, Vec<Row<'static>>) {
+    // Keys in the order they are first seen.
+    let mut keys: Vec<String> = vec![];
+    let mut seen: HashSet<String> = HashSet::new();
+    let records: Vec<HashMap<String, Cell<'static>>> = records
+        .into_iter()
+        .map(|fields| {
+            fields
+                .into_iter()
+                .enumerate()
+                .map(|(idx, field)| {
+                    let key = field.key().unwrap_or_else(|| idx.to_string());
+                    if seen.insert(key.clone()) {
+                        keys.push(key.clone());
+                    }
+                    (key, field.cell)
+                })
+                .collect()
+        })
+        .collect();
+    let rows = records
+        .into_iter()
+        .map(|mut record| {
+            new_row(keys.iter().map(|key| {
+                record
+                    .remove(key)
+                    .unwrap_or_else(|| Cell::from(missing.to_owned()))
+            }))
+        })
+        .collect();
+    (keys, rows)
+}
+
+fn new_row(cells: impl IntoIterator<Item = Cell<'static>>) -> Row<'static> {
+    let mut row = Row::new();
+    for cell in cells {
+        row.add_cell(cell);
+    }
+    row
+}
+
+/// A cell from a record, along with the names of the fields (or map keys) it came from.
+#[derive(Clone)]
+struct Field {
+    /// e.g. `["parent", "child"]` for a field of a flattened struct. Empty if the value has no
+    /// name, like the fields of a tuple.
+    path: Vec<String>,
+    cell: Cell<'static>,
+}
+
+impl Field {
+    /// The name of the field, with the fields of nested structs named `parent.child`.
+    fn key(&self) -> Option<String> {
+        (!self.path.is_empty()).then(|| self.path.join("."))
+    }
+}
+
+/// Part of a record: either a single field, or a sequence that has been exploded into rows.
+enum Slot {
+    Field(Field),
+    Rows {
+        path: Vec<String>,
+        rows: Vec<Vec<Field>>,
+    },
+}
+
+/// Serialize a record into the fields of one or more rows.
+///
+/// Records only have more than one row if they contain sequences that are exploded.
+fn serialize_rows(value: impl Serialize, options: &SerdeOptions) -> Result<Vec<Vec<Field>>> {
+    let mut serializer = Serializer::new(options, vec![], 0);
     value.serialize(&mut serializer)?;
-    Ok(serializer.row)
+    Ok(serializer.into_rows())
 }
 
-pub struct Serializer {
-    row: Row<'static>,
+pub struct Serializer<'o> {
+    options: &'o SerdeOptions,
+    slots: Vec<Slot>,
+    /// The path of the field (or map key) of the record currently being serialized.
+    path: Vec<String>,
+    /// How many structs, maps or sequences we are inside.
     level: usize,
 }
 
-impl Serializer {
+impl<'o> Serializer<'o> {
+    fn new(options: &'o SerdeOptions, path: Vec<String>, level: usize) -> Self {
+        Self {
+            options,
+            slots: vec![],
+            path,
+            level,
+        }
+    }
+
     fn serialize_static_str(&mut self, s: &'static str) -> Result<()> {
-        self.row.add_cell(Cell::from(s));
-        Ok(())
+        self.add_cell(Cell::from(s))
     }
 
     fn serialize_string(&mut self, s: String) -> Result<()> {
-        self.row.add_cell(Cell::from(s));
+        self.add_cell(Cell::from(s))
+    }
+
+    fn serialize_integer(&mut self, v: impl fmt::Display) -> Result<()> {
+        self.serialize_number(v.to_string())
+    }
+
+    fn serialize_float(&mut self, v: impl fmt::Display) -> Result<()> {
+        match self.options.precision {
+            Some(precision) => self.serialize_number(format!("{v:.precision$}")),
+            None => self.serialize_number(v.to_string()),
+        }
+    }
+
+    fn serialize_number(&mut self, mut text: String) -> Result<()> {
+        if let Some(separator) = self.options.thousands_separator {
+            text = separate_thousands(&text, separator);
+        }
+        let mut cell = Cell::from(text);
+        if self.options.align_numbers {
+            cell.set_alignment(Alignment::Right);
+        }
+        self.add_cell(cell)
+    }
+
+    fn add_cell(&mut self, cell: Cell<'static>) -> Result<()> {
+        self.slots.push(Slot::Field(Field {
+            path: self.path.clone(),
+            cell,
+        }));
         Ok(())
     }
+
+    /// Turn the slots into rows, with a row for each item of the longest exploded sequence.
+    ///
+    /// Fields that aren't exploded are repeated on each row.
+    fn into_rows(self) -> Vec<Vec<Field>> {
+        let num_rows = self
+            .slots
+            .iter()
+            .map(|slot| match slot {
+                Slot::Field(_) => 1,
+                Slot::Rows { rows, .. } => rows.len(),
+            })
+            .max()
+            .unwrap_or(1)
+            .max(1);
+        (0..num_rows)
+            .map(|idx| {
+                let mut fields = vec![];
+                for slot in self.slots.iter() {
+                    match slot {
+                        Slot::Field(field) => fields.push(field.clone()),
+                        Slot::Rows { rows, .. } if idx < rows.len() => {
+                            fields.extend(rows[idx].iter().cloned())
+                        }
+                        // Fill in shorter sequences with empty cells.
+                        Slot::Rows { rows, path } => match rows.first() {
+                            Some(row) => fields.extend(row.iter().map(|field| Field {
+                                path: field.path.clone(),
+                                cell: "".into(),
+                            })),
+                            None => fields.push(Field {
+                                path: path.clone(),
+                                cell: "".into(),
+                            }),
+                        },
+                    }
+                }
+                fields
+            })
+            .collect()
+    }
 }
 
-impl<'a> ser::Serializer for &'a mut Serializer {
+impl<'a, 'o> ser::Serializer for &'a mut Serializer<'o> {
     type Ok = ();
     type Error = Error;
 
-    type SerializeSeq = SerializerSeq<'a>;
-    type SerializeTuple = SerializerSeq<'a>;
-    type SerializeTupleStruct = SerializerSeq<'a>;
-    type SerializeTupleVariant = SerializerSeq<'a>;
-    type SerializeMap = SerializerSeq<'a>;
-    type SerializeStruct = SerializerSeq<'a>;
-    type SerializeStructVariant = SerializerSeq<'a>;
+    type SerializeSeq = SerializerSeq<'a, 'o>;
+    type SerializeTuple = SerializerSeq<'a, 'o>;
+    type SerializeTupleStruct = SerializerSeq<'a, 'o>;
+    type SerializeTupleVariant = SerializerSeq<'a, 'o>;
+    type SerializeMap = SerializerSeq<'a, 'o>;
+    type SerializeStruct = SerializerSeq<'a, 'o>;
+    type SerializeStructVariant = SerializerSeq<'a, 'o>;
 
     // Here we go with the simple methods. The following 12 methods receive one
     // of the primitive types of the data model and map it to JSON by appending
     // into the output string.
     fn serialize_bool(self, v: bool) -> Result<()> {
-        self.serialize_static_str(if v { "true" } else { "false" })
+        let text = if v {
+            &self.options.true_text
+        } else {
+            &self.options.false_text
+        };
+        self.serialize_string(text.clone())
     }
 
     fn serialize_i8(self, v: i8) -> Result<()> {
-        self.serialize_string(v.to_string())
+        self.serialize_integer(v)
     }
 
     fn serialize_i16(self, v: i16) -> Result<()> {
-        self.serialize_string(v.to_string())
+        self.serialize_integer(v)
     }
 
     fn serialize_i32(self, v: i32) -> Result<()> {
-        self.serialize_string(v.to_string())
+        self.serialize_integer(v)
     }
 
     // Not particularly efficient but this is example code anyway. A more
     // performant approach would be to use the `itoa` crate.
     fn serialize_i64(self, v: i64) -> Result<()> {
-        self.serialize_string(v.to_string())
+        self.serialize_integer(v)
     }
 
     fn serialize_u8(self, v: u8) -> Result<()> {
-        self.serialize_string(v.to_string())
+        self.serialize_integer(v)
     }
 
     fn serialize_u16(self, v: u16) -> Result<()> {
-        self.serialize_string(v.to_string())
+        self.serialize_integer(v)
     }
 
     fn serialize_u32(self, v: u32) -> Result<()> {
-        self.serialize_string(v.to_string())
+        self.serialize_integer(v)
     }
 
     fn serialize_u64(self, v: u64) -> Result<()> {
-        self.serialize_string(v.to_string())
+        self.serialize_integer(v)
     }
 
     fn serialize_f32(self, v: f32) -> Result<()> {
-        self.serialize_string(v.to_string())
+        self.serialize_float(v)
     }
 
     fn serialize_f64(self, v: f64) -> Result<()> {
-        self.serialize_string(v.to_string())
+        self.serialize_float(v)
     }
 
     fn serialize_char(self, v: char) -> Result<()> {
@@ -141,7 +682,7 @@ impl<'a> ser::Serializer for &'a mut Serializer {
     }
 
     fn serialize_none(self) -> Result<()> {
-        self.serialize_static_str("")
+        self.serialize_string(self.options.none.clone())
     }
 
     fn serialize_some<T>(self, value: &T) -> Result<()>
@@ -192,11 +733,11 @@ impl<'a> ser::Serializer for &'a mut Serializer {
     }
 
     fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq> {
-        Ok(SerializerSeq::new("", BracketTy::Square, self))
+        Ok(SerializerSeq::new("", BracketTy::Square, Kind::Seq, self))
     }
 
     fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple> {
-        Ok(SerializerSeq::new("", BracketTy::Round, self))
+        Ok(SerializerSeq::new("", BracketTy::Round, Kind::Other, self))
     }
 
     // Tuple structs look just like sequences in JSON.
@@ -205,7 +746,12 @@ impl<'a> ser::Serializer for &'a mut Serializer {
         name: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeTupleStruct> {
-        Ok(SerializerSeq::new(name, BracketTy::Round, self))
+        Ok(SerializerSeq::new(
+            name,
+            BracketTy::Round,
+            Kind::Other,
+            self,
+        ))
     }
 
     // Tuple variants are represented in JSON as `{ NAME: [DATA...] }`. Again
@@ -217,12 +763,17 @@ impl<'a> ser::Serializer for &'a mut Serializer {
         variant: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeTupleVariant> {
-        Ok(SerializerSeq::new(variant, BracketTy::Round, self))
+        Ok(SerializerSeq::new(
+            variant,
+            BracketTy::Round,
+            Kind::Other,
+            self,
+        ))
     }
 
     // Maps are represented in JSON as `{ K: V, K: V, ... }`.
     fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap> {
-        Ok(SerializerSeq::new("", BracketTy::Curly, self))
+        Ok(SerializerSeq::new("", BracketTy::Curly, Kind::Map, self))
     }
 
     // Structs look just like maps in JSON. In particular, JSON requires that we
@@ -231,7 +782,7 @@ impl<'a> ser::Serializer for &'a mut Serializer {
     // Deserialize implementation is required to know what the keys are without
     // looking at the serialized data.
     fn serialize_struct(self, name: &'static str, _len: usize) -> Result<Self::SerializeStruct> {
-        Ok(SerializerSeq::new(name, BracketTy::Curly, self))
+        Ok(SerializerSeq::new(name, BracketTy::Curly, Kind::Map, self))
     }
 
     // Struct variants are represented in JSON as `{ NAME: { K: V, ... } }`.
@@ -243,7 +794,12 @@ impl<'a> ser::Serializer for &'a mut Serializer {
         variant: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeStructVariant> {
-        Ok(SerializerSeq::new(variant, BracketTy::Curly, self))
+        Ok(SerializerSeq::new(
+            variant,
+            BracketTy::Curly,
+            Kind::Other,
+            self,
+        ))
     }
 }
 
@@ -274,56 +830,136 @@ impl BracketTy {
     }
 }
 
-pub struct SerializerSeq<'a> {
+/// The kind of value a `SerializerSeq` is serializing.
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Kind {
+    Seq,
+    /// A struct or map.
+    Map,
+    /// A tuple, or an enum variant with fields.
+    Other,
+}
+
+/// How a `SerializerSeq` writes out its elements.
+enum Mode {
+    /// Each element is a separate field.
+    Fields,
+    /// Write everything as text in a single cell, with nested values as JSON.
+    Text,
+    /// Write each element as text, joined with a separator, in a single cell.
+    Join,
+    /// Each element is serialized on its own rows.
+    Explode(Vec<Vec<Field>>),
+}
+
+pub struct SerializerSeq<'a, 'o> {
     ty: BracketTy,
-    parent: &'a mut Serializer,
+    mode: Mode,
+    parent: &'a mut Serializer<'o>,
+    /// The path of the value we are serializing.
+    path: Vec<String>,
     output: String,
 }
 
-impl<'a> SerializerSeq<'a> {
-    fn new(prefix: &'static str, ty: BracketTy, parent: &'a mut Serializer) -> Self {
+impl<'a, 'o> SerializerSeq<'a, 'o> {
+    fn new(
+        prefix: &'static str,
+        ty: BracketTy,
+        kind: Kind,
+        parent: &'a mut Serializer<'o>,
+    ) -> Self {
+        // The record itself is always split into fields.
+        let mode = match (kind, parent.level) {
+            (_, 0) => Mode::Fields,
+            (Kind::Map, level) if level <= parent.options.flatten_depth => Mode::Fields,
+            (Kind::Seq, _) => match parent.options.sequences {
+                SequenceFormat::Json => Mode::Text,
+                SequenceFormat::Join(_) => Mode::Join,
+                SequenceFormat::Explode => Mode::Explode(vec![]),
+            },
+            _ => Mode::Text,
+        };
         parent.level += 1;
         Self {
             ty,
+            path: parent.path.clone(),
             parent,
-            output: if prefix.is_empty() {
-                format!("{}", ty.start())
-            } else {
-                format!("{prefix} {}", ty.start())
+            output: match mode {
+                Mode::Join => String::new(),
+                _ if prefix.is_empty() => format!("{}", ty.start()),
+                _ => format!("{prefix} {}", ty.start()),
             },
+            mode,
         }
     }
 
+    /// The path of a field of the value we are serializing.
+    fn field_path(&self, key: Option<String>) -> Vec<String> {
+        let mut path = self.path.clone();
+        path.extend(key);
+        path
+    }
+
     fn serialize_element<T>(&mut self, key: Option<&'static str>, value: &T) -> Result<()>
     where
         T: Serialize + ?Sized,
     {
-        if self.parent.level > 1 {
-            if !self.output.ends_with(self.ty.start()) {
-                self.output += ", ";
+        match &mut self.mode {
+            Mode::Fields => {
+                self.parent.path = self.field_path(key.map(String::from));
+                value.serialize(&mut *self.parent)?;
+                self.parent.path = self.path.clone();
             }
-            if let Some(key) = key {
-                self.output += &serde_json::to_string(key).map_err(Error::from)?;
-                self.output += ": ";
+            Mode::Text => {
+                if !self.output.ends_with(self.ty.start()) {
+                    self.output += ", ";
+                }
+                if let Some(key) = key {
+                    self.output += &serde_json::to_string(key).map_err(Error::from)?;
+                    self.output += ": ";
+                }
+                self.output += &serde_json::to_string(value).map_err(Error::from)?;
+            }
+            Mode::Join => {
+                if let SequenceFormat::Join(separator) = &self.parent.options.sequences {
+                    if !self.output.is_empty() {
+                        self.output += separator;
+                    }
+                }
+                self.output += &value_to_string(value)?;
+            }
+            Mode::Explode(rows) => {
+                // Items replace the sequence, so they are at the same level.
+                let level = self.parent.level - 1;
+                let mut item = Serializer::new(self.parent.options, self.path.clone(), level);
+                value.serialize(&mut item)?;
+                rows.extend(item.into_rows());
             }
-            self.output += &serde_json::to_string(value).map_err(Error::from)?;
-        } else {
-            value.serialize(&mut *self.parent)?;
         }
         Ok(())
     }
 
     fn end(mut self) -> Result<()> {
         self.parent.level -= 1;
-        if self.parent.level > 0 {
-            self.output.push(self.ty.end());
-            self.parent.serialize_string(self.output)?;
+        match self.mode {
+            Mode::Fields => Ok(()),
+            Mode::Text => {
+                self.output.push(self.ty.end());
+                self.parent.serialize_string(self.output)
+            }
+            Mode::Join => self.parent.serialize_string(self.output),
+            Mode::Explode(rows) => {
+                self.parent.slots.push(Slot::Rows {
+                    path: self.path,
+                    rows,
+                });
+                Ok(())
+            }
         }
-        Ok(())
     }
 }
 
-impl<'a> ser::SerializeSeq for SerializerSeq<'a> {
+impl<'a, 'o> ser::SerializeSeq for SerializerSeq<'a, 'o> {
     type Ok = ();
     type Error = Error;
 
@@ -341,7 +977,7 @@ impl<'a> ser::SerializeSeq for SerializerSeq<'a> {
     }
 }
 
-impl<'a> ser::SerializeTuple for SerializerSeq<'a> {
+impl<'a, 'o> ser::SerializeTuple for SerializerSeq<'a, 'o> {
     type Ok = ();
     type Error = Error;
 
@@ -357,7 +993,7 @@ impl<'a> ser::SerializeTuple for SerializerSeq<'a> {
     }
 }
 
-impl<'a> ser::SerializeTupleStruct for SerializerSeq<'a> {
+impl<'a, 'o> ser::SerializeTupleStruct for SerializerSeq<'a, 'o> {
     type Ok = ();
     type Error = Error;
 
@@ -373,7 +1009,7 @@ impl<'a> ser::SerializeTupleStruct for SerializerSeq<'a> {
     }
 }
 
-impl<'a> ser::SerializeTupleVariant for SerializerSeq<'a> {
+impl<'a, 'o> ser::SerializeTupleVariant for SerializerSeq<'a, 'o> {
     type Ok = ();
     type Error = Error;
 
@@ -389,7 +1025,7 @@ impl<'a> ser::SerializeTupleVariant for SerializerSeq<'a> {
     }
 }
 
-impl<'a> ser::SerializeMap for SerializerSeq<'a> {
+impl<'a, 'o> ser::SerializeMap for SerializerSeq<'a, 'o> {
     type Ok = ();
     type Error = Error;
 
@@ -397,14 +1033,14 @@ impl<'a> ser::SerializeMap for SerializerSeq<'a> {
     where
         T: ?Sized + Serialize,
     {
-        if self.parent.level > 1 {
+        if let Mode::Fields = self.mode {
+            self.parent.path = self.field_path(Some(value_to_string(key)?));
+        } else {
             if !self.output.ends_with(self.ty.start()) {
                 self.output += ", ";
             }
             self.output += &serde_json::to_string(key).map_err(Error::from)?;
             self.output += ": ";
-        } else {
-            // serialize nothing
         }
         Ok(())
     }
@@ -413,10 +1049,11 @@ impl<'a> ser::SerializeMap for SerializerSeq<'a> {
     where
         T: ?Sized + Serialize,
     {
-        if self.parent.level > 1 {
-            self.output += &serde_json::to_string(value).map_err(Error::from)?;
-        } else {
+        if let Mode::Fields = self.mode {
             value.serialize(&mut *self.parent)?;
+            self.parent.path = self.path.clone();
+        } else {
+            self.output += &serde_json::to_string(value).map_err(Error::from)?;
         }
         Ok(())
     }
@@ -428,7 +1065,7 @@ impl<'a> ser::SerializeMap for SerializerSeq<'a> {
 
 // Structs are like maps in which the keys are constrained to be compile-time
 // constant strings.
-impl<'a> ser::SerializeStruct for SerializerSeq<'a> {
+impl<'a, 'o> ser::SerializeStruct for SerializerSeq<'a, 'o> {
     type Ok = ();
     type Error = Error;
 
@@ -446,7 +1083,7 @@ impl<'a> ser::SerializeStruct for SerializerSeq<'a> {
 
 // Similar to `SerializeTupleVariant`, here the `end` method is responsible for
 // closing both of the curly braces opened by `serialize_struct_variant`.
-impl<'a> ser::SerializeStructVariant for SerializerSeq<'a> {
+impl<'a, 'o> ser::SerializeStructVariant for SerializerSeq<'a, 'o> {
     type Ok = ();
     type Error = Error;
 
@@ -461,3 +1098,316 @@ impl<'a> ser::SerializeStructVariant for SerializerSeq<'a> {
         self.end()
     }
 }
+
+/// Put a separator between each group of three digits before the decimal point.
+fn separate_thousands(number: &str, separator: char) -> String {
+    let digits_start = number.find(|ch: char| ch.is_ascii_digit()).unwrap_or(0);
+    let digits_end = number[digits_start..]
+        .find(|ch: char| !ch.is_ascii_digit())
+        .map_or(number.len(), |end| digits_start + end);
+    let mut out = String::from(&number[..digits_start]);
+    for (idx, ch) in number[digits_start..digits_end].chars().enumerate() {
+        if idx > 0 && (digits_end - digits_start - idx) % 3 == 0 {
+            out.push(separator);
+        }
+        out.push(ch);
+    }
+    out.push_str(&number[digits_end..]);
+    out
+}
+
+/// Write a value as text, without quotes if it is a string, and as JSON otherwise.
+fn value_to_string<T: Serialize + ?Sized>(key: &T) -> Result<String> {
+    Ok(match serde_json::to_value(key).map_err(Error::from)? {
+        serde_json::Value::String(key) => key,
+        key => key.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{SequenceFormat, SerdeOptions};
+    use crate::{Table, TableStyle};
+    use pretty_assertions::assert_eq;
+    use serde::Serialize;
+    use std::collections::BTreeMap;
+
+    #[derive(Serialize)]
+    struct Person {
+        name: &'static str,
+        age: u32,
+        pets: Vec<&'static str>,
+    }
+
+    fn render(table: Table) -> String {
+        table
+            .with_style(TableStyle::SIMPLE)
+            .with_separate_rows(false)
+            .to_string()
+    }
+
+    #[test]
+    fn header_from_fields() {
+        let people = [
+            Person {
+                name: "Ann",
+                age: 31,
+                pets: vec!["cat"],
+            },
+            Person {
+                name: "Bob",
+                age: 7,
+                pets: vec![],
+            },
+        ];
+        let expected = "+------+-----+---------+
+| name | age | pets    |
++------+-----+---------+
+| Ann  | 31  | [\"cat\"] |
+| Bob  | 7   | []      |
++------+-----+---------+
+";
+        assert_eq!(expected, render(Table::from_serde(&people).unwrap()));
+
+        let options = SerdeOptions::new().with_header(false);
+        let table = Table::from_serde_with(&people, &options).unwrap();
+        assert!(table.header().is_none());
+        assert_eq!(table.rows().len(), 2);
+    }
+
+    #[test]
+    fn record() {
+        #[derive(Serialize)]
+        struct Address {
+            street: &'static str,
+            city: &'static str,
+        }
+
+        #[derive(Serialize)]
+        struct Customer {
+            name: &'static str,
+            address: Address,
+            phone: Option<&'static str>,
+            owner: Person,
+        }
+
+        let customer = Customer {
+            name: "Acme",
+            address: Address {
+                street: "1 Main St",
+                city: "Springfield",
+            },
+            phone: None,
+            owner: Person {
+                name: "Ann",
+                age: 31,
+                pets: vec!["cat", "dog"],
+            },
+        };
+        let options = SerdeOptions::new()
+            .with_none("-")
+            .with_sequences(SequenceFormat::Explode);
+        let table = Table::from_serde_record_with(&customer, &options).unwrap();
+        let expected = "+----------+----------------+
+| field    | value          |
++----------+----------------+
+| name     | Acme           |
++----------+----------------+
+| address                   |
++----------+----------------+
+|   street | 1 Main St      |
+|   city   | Springfield    |
+| phone    | -              |
++----------+----------------+
+| owner                     |
++----------+----------------+
+|   name   | Ann            |
+|   age    |             31 |
+|   pets   | [\"cat\", \"dog\"] |
++----------+----------------+
+";
+        assert_eq!(expected, table.with_style(TableStyle::SIMPLE).to_string());
+    }
+
+    #[test]
+    fn header_from_map_keys() {
+        let record = BTreeMap::from([(1, "one"), (2, "two")]);
+        let expected = "+-----+-----+
+| 1   | 2   |
++-----+-----+
+| one | two |
++-----+-----+
+";
+        assert_eq!(expected, render(Table::from_serde([record]).unwrap()));
+
+        let table = Table::from_serde([("a", 1), ("b", 2)]).unwrap();
+        assert!(table.header().is_none());
+    }
+
+    #[test]
+    fn keyed() {
+        #[derive(Serialize)]
+        struct Pod {
+            name: &'static str,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            ip: Option<&'static str>,
+            #[serde(flatten)]
+            labels: BTreeMap<&'static str, &'static str>,
+        }
+
+        let pods = [
+            Pod {
+                name: "web",
+                ip: None,
+                labels: BTreeMap::from([("app", "nginx")]),
+            },
+            Pod {
+                name: "db",
+                ip: Some("10.0.0.2"),
+                labels: BTreeMap::from([("tier", "data")]),
+            },
+        ];
+        let options = SerdeOptions::new().with_keyed(true).with_missing("-");
+        let expected = "+------+-------+----------+------+
+| name | app   | ip       | tier |
++------+-------+----------+------+
+| web  | nginx | -        | -    |
+| db   | -     | 10.0.0.2 | data |
++------+-------+----------+------+
+";
+        assert_eq!(
+            expected,
+            render(Table::from_serde_with(&pods, &options).unwrap())
+        );
+    }
+
+    #[derive(Serialize)]
+    struct Container {
+        name: &'static str,
+        ports: Vec<u16>,
+    }
+
+    #[derive(Serialize)]
+    struct Metadata {
+        name: &'static str,
+        labels: BTreeMap<&'static str, &'static str>,
+    }
+
+    #[derive(Serialize)]
+    struct Deployment {
+        metadata: Metadata,
+        containers: Vec<Container>,
+    }
+
+    fn deployments() -> Vec<Deployment> {
+        vec![Deployment {
+            metadata: Metadata {
+                name: "web",
+                labels: BTreeMap::from([("app", "nginx")]),
+            },
+            containers: vec![
+                Container {
+                    name: "nginx",
+                    ports: vec![80, 443],
+                },
+                Container {
+                    name: "sidecar",
+                    ports: vec![],
+                },
+            ],
+        }]
+    }
+
+    #[test]
+    fn flatten() {
+        let options = SerdeOptions::new().with_flatten_depth(1);
+        let expected = r#"+---------------+------------------+--------------------------------------------------------------------+
+| metadata.name | metadata.labels  | containers                                                         |
++---------------+------------------+--------------------------------------------------------------------+
+| web           | {"app": "nginx"} | [{"name":"nginx","ports":[80,443]}, {"name":"sidecar","ports":[]}] |
++---------------+------------------+--------------------------------------------------------------------+
+"#;
+        assert_eq!(
+            expected,
+            render(Table::from_serde_with(deployments(), &options).unwrap())
+        );
+    }
+
+    #[test]
+    fn sequences() {
+        let options = SerdeOptions::new()
+            .with_flatten_depth(2)
+            .with_sequences(SequenceFormat::Explode);
+        let expected =
+            "+---------------+---------------------+-----------------+------------------+
+| metadata.name | metadata.labels.app | containers.name | containers.ports |
++---------------+---------------------+-----------------+------------------+
+| web           | nginx               | nginx           |               80 |
+| web           | nginx               | nginx           |              443 |
+| web           | nginx               | sidecar         |                  |
++---------------+---------------------+-----------------+------------------+
+";
+        assert_eq!(
+            expected,
+            render(Table::from_serde_with(deployments(), &options).unwrap())
+        );
+
+        let options = options.with_sequences(SequenceFormat::Join(", ".into()));
+        let expected = r#"+---------------+---------------------+------------------------------------------------------------------+
+| metadata.name | metadata.labels.app | containers                                                       |
++---------------+---------------------+------------------------------------------------------------------+
+| web           | nginx               | {"name":"nginx","ports":[80,443]}, {"name":"sidecar","ports":[]} |
++---------------+---------------------+------------------------------------------------------------------+
+"#;
+        assert_eq!(
+            expected,
+            render(Table::from_serde_with(deployments(), &options).unwrap())
+        );
+    }
+
+    #[test]
+    fn formatting() {
+        #[derive(Serialize)]
+        struct Stats {
+            name: &'static str,
+            count: u64,
+            ratio: Option<f64>,
+            ok: bool,
+        }
+
+        let stats = [
+            Stats {
+                name: "a",
+                count: 1234567,
+                ratio: Some(0.5),
+                ok: true,
+            },
+            Stats {
+                name: "b",
+                count: 12,
+                ratio: None,
+                ok: false,
+            },
+        ];
+        let options = SerdeOptions::new()
+            .with_precision(2)
+            .with_thousands_separator(',')
+            .with_none("-")
+            .with_bools("✓", "✗");
+        let expected = "+------+-----------+-------+----+
+| name | count     | ratio | ok |
++------+-----------+-------+----+
+| a    | 1,234,567 |  0.50 | ✓  |
+| b    |        12 | -     | ✗  |
++------+-----------+-------+----+
+";
+        assert_eq!(
+            expected,
+            render(Table::from_serde_with(&stats, &options).unwrap())
+        );
+
+        assert_eq!(super::separate_thousands("-1234.5678", '_'), "-1_234.5678");
+        assert_eq!(super::separate_thousands("123", ','), "123");
+        assert_eq!(super::separate_thousands("NaN", ','), "NaN");
+    }
+}
@@ -0,0 +1,276 @@
+use crate::{Cell, Row, Table};
+use std::{cmp::Ordering, fmt, sync::Arc};
+
+/// The direction to sort rows in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Order {
+    #[default]
+    Ascending,
+    Descending,
+}
+
+/// A function to compare the text of two cells, used with `Comparator::Custom`.
+pub type CompareFn = dyn Fn(&str, &str) -> Ordering + Send + Sync;
+
+/// How to compare the text of two cells when sorting.
+///
+/// ANSI escape sequences are removed from the text before it is compared.
+#[derive(Clone, Default)]
+pub enum Comparator {
+    /// Compare the text by unicode code point.
+    #[default]
+    String,
+    /// Compare runs of digits as numbers, so that `"file2"` comes before `"file10"`.
+    Natural,
+    /// Parse the text as a number, ignoring surrounding whitespace and any `,` or `_` separators.
+    ///
+    /// Text that isn't a number comes after all numbers, and is compared as a string.
+    Numeric,
+    /// Compare the text using a custom function.
+    Custom(Arc<CompareFn>),
+}
+
+impl Comparator {
+    /// Compare the text using a custom function.
+    pub fn custom(compare: impl Fn(&str, &str) -> Ordering + Send + Sync + 'static) -> Self {
+        Comparator::Custom(Arc::new(compare))
+    }
+
+    /// Compare two strings.
+    pub fn compare(&self, a: &str, b: &str) -> Ordering {
+        match self {
+            Comparator::String => a.cmp(b),
+            Comparator::Natural => natural_cmp(a, b).then_with(|| a.cmp(b)),
+            Comparator::Numeric => match (parse_number(a), parse_number(b)) {
+                (Some(a), Some(b)) => a.total_cmp(&b),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => a.cmp(b),
+            },
+            Comparator::Custom(compare) => compare(a, b),
+        }
+    }
+}
+
+impl fmt::Debug for Comparator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Comparator::String => f.write_str("String"),
+            Comparator::Natural => f.write_str("Natural"),
+            Comparator::Numeric => f.write_str("Numeric"),
+            Comparator::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+/// A column to sort rows by, used with `Table::sort_by_keys`.
+#[derive(Debug, Clone)]
+pub struct SortKey {
+    column: usize,
+    order: Order,
+    comparator: Comparator,
+}
+
+impl SortKey {
+    /// Sort by the given column, in ascending order, comparing text as strings.
+    pub fn new(column: usize) -> Self {
+        Self {
+            column,
+            order: Order::Ascending,
+            comparator: Comparator::String,
+        }
+    }
+
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    pub fn order(&self) -> Order {
+        self.order
+    }
+
+    pub fn with_order(mut self, order: Order) -> Self {
+        self.set_order(order);
+        self
+    }
+
+    pub fn set_order(&mut self, order: Order) -> &mut Self {
+        self.order = order;
+        self
+    }
+
+    pub fn comparator(&self) -> &Comparator {
+        &self.comparator
+    }
+
+    pub fn with_comparator(mut self, comparator: Comparator) -> Self {
+        self.set_comparator(comparator);
+        self
+    }
+
+    pub fn set_comparator(&mut self, comparator: Comparator) -> &mut Self {
+        self.comparator = comparator;
+        self
+    }
+
+    /// The text in the key's column, without ANSI escape sequences.
+    fn text<'a>(&self, row: &'a Row) -> &'a str {
+        row.cell_at_column(self.column)
+            .map(Cell::content_for_layout)
+            .unwrap_or("")
+    }
+
+    fn compare(&self, a: &Row, b: &Row) -> Ordering {
+        let ordering = self.comparator.compare(self.text(a), self.text(b));
+        match self.order {
+            Order::Ascending => ordering,
+            Order::Descending => ordering.reverse(),
+        }
+    }
+}
+
+impl<'data> Table<'data> {
+    /// Sort the rows by the text in a column, comparing text as strings.
+    ///
+    /// A cell spanning several columns is used for each of them, and rows without a cell in the
+    /// column are treated as empty. The header and footer are not moved. The sort is stable, so
+    /// rows with the same text stay in the same order.
+    pub fn sort_by_column(&mut self, column: usize, order: Order) -> &mut Self {
+        self.sort_by_keys([SortKey::new(column).with_order(order)])
+    }
+
+    /// Sort the rows by several columns.
+    ///
+    /// Rows are compared using the first key, then the next key is used to break ties, and so on.
+    /// See `sort_by_column` for details.
+    pub fn sort_by_keys(&mut self, keys: impl IntoIterator<Item = SortKey>) -> &mut Self {
+        let keys: Vec<_> = keys.into_iter().collect();
+        self.rows.sort_by(|a, b| {
+            keys.iter()
+                .map(|key| key.compare(a, b))
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or(Ordering::Equal)
+        });
+        self
+    }
+}
+
+/// Compare strings, treating runs of ASCII digits as numbers.
+fn natural_cmp(mut a: &str, mut b: &str) -> Ordering {
+    loop {
+        let (a_chunk, a_rest) = split_chunk(a);
+        let (b_chunk, b_rest) = split_chunk(b);
+        let ordering = match (is_number(a_chunk), is_number(b_chunk)) {
+            (true, true) => {
+                // Ignore leading zeros, then a longer number is larger.
+                let a_num = a_chunk.trim_start_matches('0');
+                let b_num = b_chunk.trim_start_matches('0');
+                a_num.len().cmp(&b_num.len()).then_with(|| a_num.cmp(b_num))
+            }
+            _ => a_chunk.cmp(b_chunk),
+        };
+        if ordering.is_ne() || (a_rest.is_empty() && b_rest.is_empty()) {
+            return ordering;
+        }
+        (a, b) = (a_rest, b_rest);
+    }
+}
+
+/// Split off the leading run of digits or non-digits.
+fn split_chunk(text: &str) -> (&str, &str) {
+    let digits = is_number(text);
+    let end = text
+        .find(|ch: char| ch.is_ascii_digit() != digits)
+        .unwrap_or(text.len());
+    text.split_at(end)
+}
+
+fn is_number(chunk: &str) -> bool {
+    chunk.starts_with(|ch: char| ch.is_ascii_digit())
+}
+
+/// Parse text as a number, ignoring surrounding whitespace and any `,` or `_` separators.
+pub(crate) fn parse_number(text: &str) -> Option<f64> {
+    strip_number_separators(text).parse().ok()
+}
+
+/// Remove surrounding whitespace and any `,` or `_` separators from a number.
+pub(crate) fn strip_number_separators(text: &str) -> String {
+    text.trim()
+        .chars()
+        .filter(|ch| !matches!(ch, ',' | '_'))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Comparator, Order, SortKey};
+    use crate::{Row, Table, TableStyle};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn comparators() {
+        let sorted = |comparator: Comparator, mut items: Vec<&'static str>| {
+            items.sort_by(|a, b| comparator.compare(a, b));
+            items
+        };
+        let items = vec![
+            "file10", "file2", "file02", "File1", "file1a", "10", "9.5", "-3",
+        ];
+        assert_eq!(
+            sorted(Comparator::String, items.clone()),
+            vec!["-3", "10", "9.5", "File1", "file02", "file10", "file1a", "file2"]
+        );
+        assert_eq!(
+            sorted(Comparator::Natural, items.clone()),
+            vec!["-3", "9.5", "10", "File1", "file1a", "file02", "file2", "file10"]
+        );
+        assert_eq!(
+            sorted(
+                Comparator::Numeric,
+                vec!["1,000", " 20 ", "n/a", "3.5", "-1"]
+            ),
+            vec!["-1", "3.5", " 20 ", "1,000", "n/a"]
+        );
+        let by_len = Comparator::custom(|a, b| a.len().cmp(&b.len()));
+        assert_eq!(
+            sorted(by_len, vec!["ccc", "a", "bb"]),
+            vec!["a", "bb", "ccc"]
+        );
+    }
+
+    #[test]
+    fn sort_table() {
+        let mut table = Table::new()
+            .with_style(TableStyle::SIMPLE)
+            .with_separate_rows(false)
+            .with_header(Row::new().with_cell("Name").with_cell("Size"))
+            .with_row(Row::new().with_cell("b").with_cell("10"))
+            .with_row(Row::new().with_cell("\x1b[31mc\x1b[0m").with_cell("9"))
+            .with_row(Row::new().with_cell("a").with_cell("10"));
+
+        table.sort_by_column(0, Order::Descending);
+        let names: Vec<_> = table
+            .rows()
+            .iter()
+            .map(|row| row.cells()[0].content())
+            .collect();
+        assert_eq!(names, vec!["\x1b[31mc\x1b[0m", "b", "a"]);
+
+        table.sort_by_keys([
+            SortKey::new(1)
+                .with_comparator(Comparator::Numeric)
+                .with_order(Order::Descending),
+            SortKey::new(0),
+        ]);
+        let expected = "+------+------+
+| Name | Size |
++------+------+
+| a    | 10   |
+| b    | 10   |
+| \x1b[31mc\x1b[0m    | 9    |
++------+------+
+";
+        assert_eq!(expected, table.to_string());
+    }
+}
@@ -159,7 +159,6 @@ impl TableStyle {
     /// │ t is going to wrap to the next line                                             │
     /// ╚─────────────────────────────────────────────────────────────────────────────────╝
     /// </pre>
-
     pub const ELEGANT: TableStyle = TableStyle {
         top_left_corner: '╔',
         top_right_corner: '╗',
@@ -231,6 +230,11 @@ impl TableStyle {
         horizontal: ' ',
     };
 
+    /// Whether the vertical border is drawn using a visible character.
+    pub(crate) fn has_visible_vertical(&self) -> bool {
+        !matches!(self.vertical, ' ' | '\0')
+    }
+
     pub(crate) fn border_width(&self) -> usize {
         self.vertical.width().unwrap_or(0)
     }
@@ -0,0 +1,309 @@
+use crate::{cell::ANSI_ESC_RE, Layout, Rendered, Renderer, Table};
+use std::fmt;
+use unicode_width::UnicodeWidthStr;
+
+/// Draws a table as an SVG image.
+///
+/// The table is laid out and drawn exactly as it would be in the terminal, using monospace text and
+/// the box characters from the table's style. Colors and text styles from ANSI escape sequences in
+/// cells are kept.
+#[derive(Debug, Clone, Copy)]
+pub struct Svg {
+    font_size: usize,
+}
+
+impl Default for Svg {
+    fn default() -> Self {
+        Self { font_size: 14 }
+    }
+}
+
+impl Svg {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// The font size in pixels.
+    ///
+    /// Defaults to `14`.
+    pub fn with_font_size(mut self, font_size: usize) -> Self {
+        self.set_font_size(font_size);
+        self
+    }
+
+    /// The font size in pixels.
+    ///
+    /// Defaults to `14`.
+    pub fn set_font_size(&mut self, font_size: usize) -> &mut Self {
+        self.font_size = font_size;
+        self
+    }
+}
+
+impl Renderer for Svg {
+    fn border_width(&self, table: &Table) -> usize {
+        table.style.border_width()
+    }
+
+    fn render(&self, table: &Table, layout: &Layout, f: &mut dyn fmt::Write) -> fmt::Result {
+        let mut text = String::new();
+        table.render_box(&table.box_style(), layout, &mut text)?;
+        let lines: Vec<Vec<Span>> = text.lines().map(parse_line).collect();
+
+        let char_width = self.font_size as f64 * 0.6;
+        let line_height = px(self.font_size as f64 * 1.2);
+        let columns = lines
+            .iter()
+            .map(|line| line.iter().map(|span| span.text.width()).sum::<usize>())
+            .max()
+            .unwrap_or(0);
+        let width = px(columns as f64 * char_width);
+        let height = px(lines.len() as f64 * line_height);
+
+        writeln!(
+            f,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+        )?;
+        writeln!(
+            f,
+            "<style>text {{ font-family: monospace; font-size: {}px; white-space: pre; }}</style>",
+            self.font_size
+        )?;
+        for (line_idx, line) in lines.iter().enumerate() {
+            let top = px(line_idx as f64 * line_height);
+            // Backgrounds have to be drawn first so they are behind the text.
+            let mut column = 0;
+            for span in line.iter() {
+                let span_width = span.text.width();
+                if let Some(bg) = &span.style.bg {
+                    writeln!(
+                        f,
+                        r#"<rect x="{}" y="{top}" width="{}" height="{line_height}" fill="{bg}"/>"#,
+                        px(column as f64 * char_width),
+                        px(span_width as f64 * char_width),
+                    )?;
+                }
+                column += span_width;
+            }
+
+            // Place text on the baseline, roughly 80% of the way down the line.
+            write!(f, r#"<text y="{}">"#, px(top + line_height * 0.8))?;
+            let mut column = 0;
+            for span in line.iter() {
+                write!(f, r#"<tspan x="{}""#, px(column as f64 * char_width))?;
+                span.style.write_attributes(f)?;
+                f.write_char('>')?;
+                write_escaped(&span.text, f)?;
+                f.write_str("</tspan>")?;
+                column += span.text.width();
+            }
+            writeln!(f, "</text>")?;
+        }
+        writeln!(f, "</svg>")
+    }
+}
+
+impl<'data> Table<'data> {
+    /// Draw the table as an SVG image.
+    pub fn to_svg(&self) -> Rendered<'_, Svg> {
+        self.render_with(Svg::new())
+    }
+}
+
+impl Rendered<'_, Svg> {
+    /// The font size in pixels.
+    ///
+    /// Defaults to `14`.
+    pub fn with_font_size(mut self, font_size: usize) -> Self {
+        self.renderer.set_font_size(font_size);
+        self
+    }
+}
+
+/// Round a coordinate to avoid writing out floating point noise.
+fn px(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+/// A piece of text with a single style.
+struct Span {
+    text: String,
+    style: Style,
+}
+
+/// Text style, set using ANSI "Select Graphic Rendition" escape sequences.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct Style {
+    fg: Option<String>,
+    bg: Option<String>,
+    bold: bool,
+    italic: bool,
+    underline: bool,
+}
+
+impl Style {
+    /// Update the style using the parameters of an SGR escape sequence (e.g. `1;31`).
+    fn apply(&mut self, params: &str) {
+        let mut params = params.split(';').map(|p| p.parse::<u8>().unwrap_or(0));
+        while let Some(param) = params.next() {
+            match param {
+                0 => *self = Style::default(),
+                1 => self.bold = true,
+                3 => self.italic = true,
+                4 => self.underline = true,
+                22 => self.bold = false,
+                23 => self.italic = false,
+                24 => self.underline = false,
+                30..=37 => self.fg = Some(palette(param - 30)),
+                38 => self.fg = extended_color(&mut params),
+                39 => self.fg = None,
+                40..=47 => self.bg = Some(palette(param - 40)),
+                48 => self.bg = extended_color(&mut params),
+                49 => self.bg = None,
+                90..=97 => self.fg = Some(palette(param - 90 + 8)),
+                100..=107 => self.bg = Some(palette(param - 100 + 8)),
+                _ => (),
+            }
+        }
+    }
+
+    fn write_attributes(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        if let Some(fg) = &self.fg {
+            write!(f, r#" fill="{fg}""#)?;
+        }
+        if self.bold {
+            f.write_str(r#" font-weight="bold""#)?;
+        }
+        if self.italic {
+            f.write_str(r#" font-style="italic""#)?;
+        }
+        if self.underline {
+            f.write_str(r#" text-decoration="underline""#)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parse the color following a `38` or `48` parameter.
+///
+/// This is either `5;n` for the 256-color palette or `2;r;g;b` for true color.
+fn extended_color(params: &mut impl Iterator<Item = u8>) -> Option<String> {
+    match params.next()? {
+        5 => params.next().map(palette),
+        2 => Some(format!(
+            "#{:02x}{:02x}{:02x}",
+            params.next()?,
+            params.next()?,
+            params.next()?
+        )),
+        _ => None,
+    }
+}
+
+/// The xterm 256-color palette.
+fn palette(idx: u8) -> String {
+    const BASIC: [&str; 16] = [
+        "#000000", "#cd0000", "#00cd00", "#cdcd00", "#0000ee", "#cd00cd", "#00cdcd", "#e5e5e5",
+        "#7f7f7f", "#ff0000", "#00ff00", "#ffff00", "#5c5cff", "#ff00ff", "#00ffff", "#ffffff",
+    ];
+    match idx {
+        0..=15 => BASIC[usize::from(idx)].to_string(),
+        16..=231 => {
+            let level = |n: u8| if n == 0 { 0 } else { 55 + n * 40 };
+            let idx = idx - 16;
+            format!(
+                "#{:02x}{:02x}{:02x}",
+                level(idx / 36),
+                level((idx / 6) % 6),
+                level(idx % 6)
+            )
+        }
+        232..=255 => {
+            let gray = 8 + (idx - 232) * 10;
+            format!("#{gray:02x}{gray:02x}{gray:02x}")
+        }
+    }
+}
+
+/// Split a line of text into spans with the same style, removing the escape sequences.
+fn parse_line(line: &str) -> Vec<Span> {
+    let mut spans = vec![];
+    let mut style = Style::default();
+    let mut last = 0;
+    for esc in ANSI_ESC_RE.find_iter(line) {
+        if last < esc.start() {
+            spans.push(Span {
+                text: line[last..esc.start()].to_string(),
+                style: style.clone(),
+            });
+        }
+        last = esc.end();
+        let esc = esc.as_str();
+        if let Some(params) = esc.strip_suffix('m') {
+            let params = params
+                .strip_prefix("\x1b[")
+                .or_else(|| params.strip_prefix('\u{9b}'));
+            if let Some(params) = params {
+                style.apply(params);
+            }
+        }
+    }
+    if last < line.len() {
+        spans.push(Span {
+            text: line[last..].to_string(),
+            style,
+        });
+    }
+    spans
+}
+
+/// Write out text, escaping characters that are special in XML.
+///
+/// Null characters (used by `TableStyle::BLANK`) are not allowed in XML, so are replaced with
+/// spaces.
+fn write_escaped(text: &str, f: &mut dyn fmt::Write) -> fmt::Result {
+    for ch in text.chars() {
+        match ch {
+            '&' => f.write_str("&amp;")?,
+            '<' => f.write_str("&lt;")?,
+            '>' => f.write_str("&gt;")?,
+            '"' => f.write_str("&quot;")?,
+            '\0' => f.write_char(' ')?,
+            ch => f.write_char(ch)?,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Cell, Row, Table, TableStyle};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn svg() {
+        let table = Table::new().with_style(TableStyle::THIN).with_row(
+            Row::new()
+                .with_cell(Cell::from("\x1b[1;31mred\x1b[0m"))
+                .with_cell("<b>"),
+        );
+        let expected = r##"<svg xmlns="http://www.w3.org/2000/svg" width="78" height="36" viewBox="0 0 78 36">
+<style>text { font-family: monospace; font-size: 10px; white-space: pre; }</style>
+<text y="9.6"><tspan x="0">┌─────┬─────┐</tspan></text>
+<text y="21.6"><tspan x="0">│ </tspan><tspan x="12" fill="#cd0000" font-weight="bold">red</tspan><tspan x="30"> │ &lt;b&gt; │</tspan></text>
+<text y="33.6"><tspan x="0">└─────┴─────┘</tspan></text>
+</svg>
+"##;
+        assert_eq!(expected, table.to_svg().with_font_size(10).to_string());
+    }
+
+    #[test]
+    fn background() {
+        let table = Table::new()
+            .with_style(TableStyle::BLANK)
+            .with_row(Row::new().with_cell(Cell::from("\x1b[48;5;196mx\x1b[0m")));
+        let svg = table.to_svg().with_font_size(10).to_string();
+        assert!(svg.contains(r##"<rect x="12" y="12" width="6" height="12" fill="#ff0000"/>"##));
+        assert!(!svg.contains('\0'));
+    }
+}
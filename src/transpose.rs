@@ -0,0 +1,77 @@
+use crate::{Cell, Row, Table};
+
+impl<'data> Table<'data> {
+    /// Swap rows and columns, so each column becomes a row.
+    ///
+    /// The header becomes the first column and the footer becomes the last, and the new table
+    /// has neither. Like `select_columns`, a cell spanning several columns is repeated in each of
+    /// the rows it ends up in. Missing cells at the end of short rows are left empty.
+    pub fn transpose(&mut self) -> &mut Self {
+        let num_columns = self.num_columns();
+        let rows: Vec<Row<'data>> = self
+            .header
+            .take()
+            .into_iter()
+            .chain(std::mem::take(&mut self.rows))
+            .chain(self.footer.take())
+            .collect();
+        self.rows = (0..num_columns)
+            .map(|column| {
+                let mut new_row = Row::new();
+                for row in rows.iter() {
+                    let cell = match row.cell_at_column(column) {
+                        Some(cell) => cell.clone().with_col_span(1),
+                        None => Cell::from(""),
+                    };
+                    new_row.add_cell(cell);
+                }
+                new_row
+            })
+            .collect();
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Alignment, Cell, Row, Table, TableStyle};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn transpose() {
+        let mut table = Table::new()
+            .with_style(TableStyle::SIMPLE)
+            .with_separate_rows(false)
+            .with_header(
+                Row::new()
+                    .with_cell("setting")
+                    .with_cell("dev")
+                    .with_cell("prod"),
+            )
+            .with_row(
+                Row::new()
+                    .with_cell("threads")
+                    .with_cell(Cell::from("4").with_alignment(Alignment::Right))
+                    .with_cell(Cell::from("16").with_alignment(Alignment::Right)),
+            )
+            .with_row(
+                Row::new()
+                    .with_cell("log")
+                    .with_cell(Cell::from("debug").with_col_span(2)),
+            )
+            .with_row(Row::new().with_cell("tls"));
+        table.transpose();
+        let expected = "+---------+---------+-------+-----+
+| setting | threads | log   | tls |
+| dev     |       4 | debug |     |
+| prod    |      16 | debug |     |
++---------+---------+-------+-----+
+";
+        assert_eq!(expected, table.to_string());
+        assert!(table.header().is_none());
+
+        table.transpose();
+        assert_eq!(table.rows().len(), 4);
+        assert_eq!(table.rows()[2].cells().len(), 3);
+    }
+}
@@ -1,6 +1,12 @@
 use proc_macro2::TokenStream;
-use quote::{quote, ToTokens};
-use syn::{parse_macro_input, Data, DeriveInput, Fields, Generics, Ident};
+use quote::{format_ident, quote, ToTokens};
+use syn::{
+    ext::IdentExt,
+    parse::{Parse, ParseStream},
+    parse_macro_input,
+    punctuated::Punctuated,
+    Attribute, Data, DeriveInput, Fields, Generics, Ident, LitInt, LitStr, Member, Path, Token,
+};
 
 macro_rules! bail {
     ($span:expr, $fmt:expr $(,$args:expr)*) => {
@@ -10,7 +16,107 @@ macro_rules! bail {
     }
 }
 
-#[proc_macro_derive(IntoRow)]
+/// Derive `IntoRow` for a struct, with a column for each field.
+///
+/// The header of each column is the name of the field, and each cell is written using
+/// `Display`. This can be changed with `#[table(...)]` attributes.
+///
+/// For enums, the first column is `variant`, containing the name of the variant, followed by a
+/// column for each field of any variant. Fields with the same header in different variants share
+/// a column, and cells for fields a variant doesn't have are empty.
+///
+/// On the struct or enum:
+///
+/// - `rename_all = "..."`: change the case of the headers. One of `"lowercase"`, `"UPPERCASE"`,
+///   `"PascalCase"`, `"camelCase"`, `"snake_case"`, `"SCREAMING_SNAKE_CASE"`, `"kebab-case"`,
+///   `"SCREAMING-KEBAB-CASE"` or `"Title Case"`.
+/// - `per_variant` (enums only): only have columns for the fields of the variant, so `headers`
+///   depends on the variant. This is for tables where every row is the same variant.
+///
+/// On enum variants:
+///
+/// - `rename = "..."`: use the given text in the `variant` column.
+///
+/// On fields:
+///
+/// - `rename = "..."`: use the given header.
+/// - `skip`: don't show the field.
+/// - `align = "..."`: align the cell `"left"`, `"right"` or `"center"`.
+/// - `format = "..."`: write the cell with a format string, e.g. `"{:.2}"`.
+/// - `with = path::to::function`: write the cell using a function, which is given a reference to
+///   the field and returns anything that can be turned into a `Cell`.
+/// - `order = N`: fields with an `order` come first, sorted by it. Other fields follow in the
+///   order they are declared.
+///
+/// # Example
+///
+/// ```
+/// use term_data_table::{data_table, IntoRow, TableStyle};
+///
+/// #[derive(IntoRow)]
+/// #[table(rename_all = "Title Case")]
+/// struct Order {
+///     #[table(order = 0)]
+///     order_id: u32,
+///     #[table(align = "right", format = "{:.2}")]
+///     unit_price: f64,
+///     #[table(skip)]
+///     internal_note: String,
+///     #[table(rename = "Shipped?", with = yes_no)]
+///     shipped: bool,
+/// }
+///
+/// fn yes_no(value: &bool) -> &'static str {
+///     if *value { "yes" } else { "no" }
+/// }
+///
+/// let orders = [Order {
+///     order_id: 7,
+///     unit_price: 3.5,
+///     internal_note: "fragile".into(),
+///     shipped: true,
+/// }];
+/// let table = data_table(&orders)
+///     .with_header(orders[0].headers())
+///     .with_style(TableStyle::SIMPLE);
+/// let expected = "\
+/// +----------+------------+----------+
+/// | Order Id | Unit Price | Shipped? |
+/// +----------+------------+----------+
+/// | 7        |       3.50 | yes      |
+/// +----------+------------+----------+
+/// ";
+/// assert_eq!(table.to_string(), expected);
+///
+/// #[derive(IntoRow)]
+/// enum Event {
+///     Login { user: String },
+///     #[table(rename = "logout")]
+///     Logout { user: String, reason: &'static str },
+///     Restart,
+/// }
+///
+/// let events = [
+///     Event::Login { user: "ann".into() },
+///     Event::Logout { user: "ann".into(), reason: "idle" },
+///     Event::Restart,
+/// ];
+/// let table = data_table(&events)
+///     .with_header(events[0].headers())
+///     .with_style(TableStyle::SIMPLE)
+///     .with_separate_rows(false);
+/// let expected = "\
+/// +---------+------+--------+
+/// | variant | user | reason |
+/// +---------+------+--------+
+/// | Login   | ann  |        |
+/// | logout  | ann  | idle   |
+/// | Restart |      |        |
+/// +---------+------+--------+
+/// ";
+/// assert_eq!(table.to_string(), expected);
+/// ```
+#[proc_macro_derive(IntoRow, attributes(table))]
 pub fn my_macro(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     let input = match IntoRowInput::from_derive(input) {
@@ -24,61 +130,87 @@ pub fn my_macro(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
 struct IntoRowInput {
     name: Ident,
     generics: Generics,
-    fields: IntoRowFields,
+    body: IntoRowBody,
+}
+
+enum IntoRowBody {
+    Struct(Vec<IntoRowField>),
+    Enum {
+        /// The header of the column containing the name of the variant.
+        variant_header: String,
+        variants: Vec<IntoRowVariant>,
+        /// Whether each variant only has columns for its own fields.
+        per_variant: bool,
+    },
 }
 
 impl IntoRowInput {
     fn from_derive(input: DeriveInput) -> Result<Self, syn::Error> {
-        let struct_ = match input.data {
-            Data::Struct(v) => v,
-            Data::Enum(e) => {
-                return Err(syn::Error::new(
-                    e.enum_token.span,
-                    "can only derive this trait on structs",
-                ))
-            }
-            Data::Union(u) => {
-                return Err(syn::Error::new(
-                    u.union_token.span,
-                    "can only derive this trait on structs",
-                ))
+        let mut rename_all = None;
+        let mut per_variant = None;
+        for attr in table_attrs(&input.attrs)? {
+            match attr {
+                TableAttr::RenameAll(case) => {
+                    if rename_case(&case.value(), "a").is_none() {
+                        bail!(case.span(), "unknown case `{}`", case.value());
+                    }
+                    rename_all = Some(case.value());
+                }
+                TableAttr::PerVariant(ident) => per_variant = Some(ident),
+                attr => bail!(
+                    attr.name().span(),
+                    "`{}` can only be used on fields",
+                    attr.name()
+                ),
             }
-        };
+        }
+        let rename_all = rename_all.as_deref();
 
-        let fields = match struct_.fields {
-            Fields::Named(fields) => {
-                if fields.named.is_empty() {
+        let body = match input.data {
+            Data::Struct(struct_) => {
+                if let Some(ident) = per_variant {
+                    bail!(ident.span(), "`per_variant` can only be used on enums");
+                }
+                if struct_.fields.is_empty() {
                     bail!(
                         struct_.struct_token.span,
                         "no data to display for zero-sized types"
                     );
                 }
-                IntoRowFields::Named(
-                    fields
-                        .named
-                        .into_iter()
-                        .map(|field| (field.ident.unwrap()))
-                        .collect(),
-                )
+                IntoRowBody::Struct(IntoRowField::from_fields(struct_.fields, rename_all)?)
             }
-            Fields::Unnamed(fields) => {
-                if fields.unnamed.is_empty() {
+            Data::Enum(enum_) => {
+                if enum_.variants.is_empty() {
                     bail!(
-                        struct_.struct_token.span,
+                        enum_.enum_token.span,
                         "no data to display for zero-sized types"
                     );
                 }
-                IntoRowFields::Unnamed(fields.unnamed.len())
+                let variants = enum_
+                    .variants
+                    .into_iter()
+                    .map(|variant| IntoRowVariant::from_variant(variant, rename_all))
+                    .collect::<Result<_, _>>()?;
+                IntoRowBody::Enum {
+                    variant_header: match rename_all {
+                        Some(case) => rename_case(case, "variant").unwrap(),
+                        None => "variant".into(),
+                    },
+                    variants,
+                    per_variant: per_variant.is_some(),
+                }
+            }
+            Data::Union(u) => {
+                return Err(syn::Error::new(
+                    u.union_token.span,
+                    "can only derive this trait on structs and enums",
+                ))
             }
-            Fields::Unit => bail!(
-                struct_.struct_token.span,
-                "no data to display for zero-sized types"
-            ),
         };
 
         Ok(Self {
             name: input.ident,
-            fields,
+            body,
             generics: input.generics,
         })
     }
@@ -88,8 +220,60 @@ impl ToTokens for IntoRowInput {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let name = &self.name;
         let (g_impl, g_type, g_where) = self.generics.split_for_impl();
-        let headers = self.fields.headers();
-        let into_row = self.fields.into_row();
+        let (headers, into_row) = match &self.body {
+            IntoRowBody::Struct(fields) => {
+                let headers = fields.iter().map(|field| cell_text(&field.header));
+                let cells = fields.iter().map(|field| {
+                    let member = &field.member;
+                    field.cell(quote! { self.#member })
+                });
+                (new_row(headers), new_row(cells))
+            }
+            IntoRowBody::Enum {
+                variant_header,
+                variants,
+                per_variant: true,
+            } => {
+                let headers = variants.iter().map(|variant| {
+                    let headers = variant.fields.iter().map(|field| cell_text(&field.header));
+                    let row = new_row(std::iter::once(cell_text(variant_header)).chain(headers));
+                    let pattern = variant.pattern(false);
+                    quote! { #pattern => #row, }
+                });
+                let rows = variants.iter().map(|variant| {
+                    let cells = variant.fields.iter().map(IntoRowVariant::binding_cell);
+                    let row = new_row(std::iter::once(cell_text(&variant.name)).chain(cells));
+                    let pattern = variant.pattern(true);
+                    quote! { #pattern => #row, }
+                });
+                (
+                    quote! { match self { #(#headers)* } },
+                    quote! { match self { #(#rows)* } },
+                )
+            }
+            IntoRowBody::Enum {
+                variant_header,
+                variants,
+                per_variant: false,
+            } => {
+                let columns = union_columns(variants);
+                let headers = std::iter::once(variant_header)
+                    .chain(columns.iter().copied())
+                    .map(|header| cell_text(header));
+                let rows = variants.iter().map(|variant| {
+                    let cells = columns.iter().map(|header| {
+                        match variant.fields.iter().find(|field| &field.header == *header) {
+                            Some(field) => IntoRowVariant::binding_cell(field),
+                            None => cell_text(""),
+                        }
+                    });
+                    let row = new_row(std::iter::once(cell_text(&variant.name)).chain(cells));
+                    let pattern = variant.pattern(true);
+                    quote! { #pattern => #row, }
+                });
+                (new_row(headers), quote! { match self { #(#rows)* } })
+            }
+        };
 
         tokens.extend(quote! {
             impl #g_impl ::term_data_table::IntoRow for #name #g_type #g_where {
@@ -105,55 +289,376 @@ impl ToTokens for IntoRowInput {
     }
 }
 
-enum IntoRowFields {
-    Named(Vec<Ident>),
-    Unnamed(usize),
+/// The headers of the fields of all variants, with fields with the same header sharing a
+/// column.
+///
+/// Columns are in the order they are first seen, then sorted by the `order` of the first field
+/// in each.
+fn union_columns(variants: &[IntoRowVariant]) -> Vec<&String> {
+    let mut columns: Vec<(&String, Option<i64>)> = vec![];
+    for field in variants.iter().flat_map(|variant| &variant.fields) {
+        if !columns.iter().any(|(header, _)| *header == &field.header) {
+            columns.push((&field.header, field.order));
+        }
+    }
+    columns.sort_by_key(|(_, order)| (order.is_none(), *order));
+    columns.into_iter().map(|(header, _)| header).collect()
 }
 
-impl IntoRowFields {
-    fn headers(&self) -> TokenStream {
-        match self {
-            Self::Named(idents) => {
-                let idents = idents.into_iter().map(|ident| ident.to_string());
-                quote! {
-                    ::term_data_table::Row::new()
-                    #(
-                        .with_cell(::term_data_table::Cell::from(#idents))
-                    )*
-                }
-            }
-            Self::Unnamed(count) => {
-                let idents = (0..*count).map(|idx| idx.to_string());
-                quote! {
-                    ::term_data_table::Row::new()
-                    #(
-                        .with_cell(::term_data_table::Cell::from(#idents))
-                    )*
-                }
+fn new_row(cells: impl Iterator<Item = TokenStream>) -> TokenStream {
+    quote! {
+        ::term_data_table::Row::new()
+        #(
+            .with_cell(#cells)
+        )*
+    }
+}
+
+fn cell_text(text: &str) -> TokenStream {
+    quote! { ::term_data_table::Cell::from(#text) }
+}
+
+struct IntoRowVariant {
+    ident: Ident,
+    /// The text in the variant column.
+    name: String,
+    fields: Vec<IntoRowField>,
+}
+
+impl IntoRowVariant {
+    fn from_variant(variant: syn::Variant, rename_all: Option<&str>) -> Result<Self, syn::Error> {
+        let mut name = variant.ident.unraw().to_string();
+        for attr in table_attrs(&variant.attrs)? {
+            match attr {
+                TableAttr::Rename(lit) => name = lit.value(),
+                attr => bail!(
+                    attr.name().span(),
+                    "`{}` can't be used on variants",
+                    attr.name()
+                ),
             }
         }
+        Ok(Self {
+            ident: variant.ident,
+            name,
+            fields: IntoRowField::from_fields(variant.fields, rename_all)?,
+        })
     }
 
-    fn into_row(&self) -> TokenStream {
-        match self {
-            Self::Named(idents) => {
-                let idents = idents.into_iter();
-                quote! {
-                    ::term_data_table::Row::new()
-                    #(
-                        .with_cell(::term_data_table::Cell::from(self.#idents.to_string()))
-                    )*
-                }
+    /// A pattern matching this variant, optionally binding each field that isn't skipped.
+    fn pattern(&self, bind: bool) -> TokenStream {
+        let ident = &self.ident;
+        let fields = self.fields.iter().filter(|_| bind).map(|field| {
+            let member = &field.member;
+            let binding = Self::binding(field);
+            quote! { #member: #binding, }
+        });
+        quote! { Self::#ident { #(#fields)* .. } }
+    }
+
+    fn binding(field: &IntoRowField) -> Ident {
+        match &field.member {
+            Member::Named(ident) => format_ident!("__field_{}", ident.unraw()),
+            Member::Unnamed(idx) => format_ident!("__field_{}", idx.index),
+        }
+    }
+
+    fn binding_cell(field: &IntoRowField) -> TokenStream {
+        let binding = Self::binding(field);
+        field.cell(quote! { (*#binding) })
+    }
+}
+
+struct IntoRowField {
+    member: Member,
+    header: String,
+    align: Option<Ident>,
+    format: Option<LitStr>,
+    with: Option<Path>,
+    order: Option<i64>,
+}
+
+impl IntoRowField {
+    /// The fields that aren't skipped, in order.
+    fn from_fields(fields: Fields, rename_all: Option<&str>) -> Result<Vec<Self>, syn::Error> {
+        let mut fields = fields
+            .into_iter()
+            .enumerate()
+            .map(|(idx, field)| IntoRowField::from_field(idx, field, rename_all))
+            .filter_map(Result::transpose)
+            .collect::<Result<Vec<_>, _>>()?;
+        // Fields with an order come first, and the sort is stable so others stay in order.
+        fields.sort_by_key(|field| (field.order.is_none(), field.order));
+        Ok(fields)
+    }
+
+    /// Returns `None` if the field is skipped.
+    fn from_field(
+        idx: usize,
+        field: syn::Field,
+        rename_all: Option<&str>,
+    ) -> Result<Option<Self>, syn::Error> {
+        let (member, mut header) = match field.ident {
+            Some(ident) => {
+                let name = ident.unraw().to_string();
+                let header = match rename_all {
+                    Some(case) => rename_case(case, &name).unwrap(),
+                    None => name,
+                };
+                (Member::Named(ident), header)
             }
-            Self::Unnamed(count) => {
-                let idents = 0..*count;
-                quote! {
-                    ::term_data_table::Row::new()
-                    #(
-                        .with_cell(::term_data_table::Cell::from(self.#idents.to_string()))
-                    )*
+            None => (Member::Unnamed(idx.into()), idx.to_string()),
+        };
+        let mut align = None;
+        let mut format = None;
+        let mut with = None;
+        let mut order = None;
+        for attr in table_attrs(&field.attrs)? {
+            match attr {
+                TableAttr::Rename(name) => header = name.value(),
+                TableAttr::Skip(_) => return Ok(None),
+                TableAttr::Align(lit) => {
+                    let variant = match lit.value().as_str() {
+                        "left" => "Left",
+                        "right" => "Right",
+                        "center" => "Center",
+                        _ => bail!(
+                            lit.span(),
+                            "expected `\"left\"`, `\"right\"` or `\"center\"`"
+                        ),
+                    };
+                    align = Some(Ident::new(variant, lit.span()));
                 }
+                TableAttr::Format(lit) => format = Some(lit),
+                TableAttr::With(path) => with = Some(path),
+                TableAttr::Order(lit) => order = Some(lit.base10_parse()?),
+                attr => bail!(
+                    attr.name().span(),
+                    "`{}` can only be used on structs and enums",
+                    attr.name()
+                ),
             }
         }
+        if let (Some(format), Some(_)) = (&format, &with) {
+            bail!(format.span(), "`format` and `with` can't be used together");
+        }
+        Ok(Some(Self {
+            member,
+            header,
+            align,
+            format,
+            with,
+            order,
+        }))
+    }
+
+    /// The cell for this field, given an expression for its value.
+    fn cell(&self, value: TokenStream) -> TokenStream {
+        let cell = if let Some(with) = &self.with {
+            quote! { ::term_data_table::Cell::from(#with(&#value)) }
+        } else if let Some(format) = &self.format {
+            quote! { ::term_data_table::Cell::from(::std::format!(#format, #value)) }
+        } else {
+            quote! { ::term_data_table::Cell::from(#value.to_string()) }
+        };
+        match &self.align {
+            Some(align) => quote! {
+                #cell.with_alignment(::term_data_table::Alignment::#align)
+            },
+            None => cell,
+        }
+    }
+}
+
+/// An option in a `#[table(...)]` attribute.
+enum TableAttr {
+    Rename(LitStr),
+    Skip(Ident),
+    Align(LitStr),
+    Format(LitStr),
+    With(Path),
+    Order(LitInt),
+    RenameAll(LitStr),
+    PerVariant(Ident),
+}
+
+impl TableAttr {
+    fn name(&self) -> Ident {
+        let name = match self {
+            Self::Rename(_) => "rename",
+            Self::Skip(_) => "skip",
+            Self::Align(_) => "align",
+            Self::Format(_) => "format",
+            Self::With(_) => "with",
+            Self::Order(_) => "order",
+            Self::RenameAll(_) => "rename_all",
+            Self::PerVariant(_) => "per_variant",
+        };
+        let span = match self {
+            Self::Skip(ident) | Self::PerVariant(ident) => ident.span(),
+            Self::With(path) => path.segments[0].ident.span(),
+            Self::Order(lit) => lit.span(),
+            Self::Rename(lit) | Self::Align(lit) | Self::Format(lit) | Self::RenameAll(lit) => {
+                lit.span()
+            }
+        };
+        Ident::new(name, span)
+    }
+}
+
+impl Parse for TableAttr {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name: Ident = input.parse()?;
+        if name == "skip" {
+            return Ok(Self::Skip(name));
+        }
+        if name == "per_variant" {
+            return Ok(Self::PerVariant(name));
+        }
+        input.parse::<Token![=]>()?;
+        Ok(match name.to_string().as_str() {
+            "rename" => Self::Rename(input.parse()?),
+            "align" => Self::Align(input.parse()?),
+            "format" => Self::Format(input.parse()?),
+            "with" => Self::With(input.parse()?),
+            "order" => Self::Order(input.parse()?),
+            "rename_all" => Self::RenameAll(input.parse()?),
+            _ => bail!(name.span(), "unknown attribute `{}`", name),
+        })
+    }
+}
+
+/// All the options in `#[table(...)]` attributes.
+fn table_attrs(attrs: &[Attribute]) -> Result<Vec<TableAttr>, syn::Error> {
+    let mut options = vec![];
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("table")) {
+        options.extend(attr.parse_args_with(Punctuated::<TableAttr, Token![,]>::parse_terminated)?);
+    }
+    Ok(options)
+}
+
+/// Change the case of a snake case field name.
+///
+/// Returns `None` if the case isn't known.
+fn rename_case(case: &str, name: &str) -> Option<String> {
+    let words = name.split('_').filter(|word| !word.is_empty());
+    let capitalize = |word: &str| {
+        let mut chars = word.chars();
+        chars
+            .next()
+            .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+            .unwrap_or_default()
+    };
+    Some(match case {
+        "lowercase" => name.replace('_', "").to_lowercase(),
+        "UPPERCASE" => name.replace('_', "").to_uppercase(),
+        "PascalCase" => words.map(capitalize).collect(),
+        "camelCase" => {
+            let pascal: String = words.map(capitalize).collect();
+            let mut chars = pascal.chars();
+            chars
+                .next()
+                .map(|first| first.to_lowercase().chain(chars).collect())
+                .unwrap_or_default()
+        }
+        "snake_case" => name.to_owned(),
+        "SCREAMING_SNAKE_CASE" => name.to_uppercase(),
+        "kebab-case" => words.collect::<Vec<_>>().join("-"),
+        "SCREAMING-KEBAB-CASE" => words.collect::<Vec<_>>().join("-").to_uppercase(),
+        "Title Case" => words.map(capitalize).collect::<Vec<_>>().join(" "),
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{rename_case, IntoRowInput};
+    use syn::{parse_quote, DeriveInput};
+
+    /// The error message from deriving on `input`.
+    fn error(input: DeriveInput) -> String {
+        match IntoRowInput::from_derive(input) {
+            Ok(_) => panic!("expected an error"),
+            Err(e) => e.to_string(),
+        }
+    }
+
+    #[test]
+    fn rename_cases() {
+        let cases = [
+            ("lowercase", "orderid"),
+            ("UPPERCASE", "ORDERID"),
+            ("PascalCase", "OrderId"),
+            ("camelCase", "orderId"),
+            ("snake_case", "order_id"),
+            ("SCREAMING_SNAKE_CASE", "ORDER_ID"),
+            ("kebab-case", "order-id"),
+            ("SCREAMING-KEBAB-CASE", "ORDER-ID"),
+            ("Title Case", "Order Id"),
+        ];
+        for (case, expected) in cases {
+            assert_eq!(rename_case(case, "order_id").as_deref(), Some(expected));
+        }
+        assert_eq!(rename_case("Sentence case", "order_id"), None);
+    }
+
+    #[test]
+    fn struct_errors() {
+        assert_eq!(
+            error(parse_quote! { struct S { #[table(bogus = "x")] a: u8 } }),
+            "unknown attribute `bogus`"
+        );
+        assert_eq!(
+            error(parse_quote! { #[table(rename_all = "Sentence case")] struct S { a: u8 } }),
+            "unknown case `Sentence case`"
+        );
+        assert_eq!(
+            error(parse_quote! { #[table(skip)] struct S { a: u8 } }),
+            "`skip` can only be used on fields"
+        );
+        assert_eq!(
+            error(parse_quote! { struct S { #[table(rename_all = "UPPERCASE")] a: u8 } }),
+            "`rename_all` can only be used on structs and enums"
+        );
+        assert_eq!(
+            error(parse_quote! { struct S { #[table(align = "top")] a: u8 } }),
+            "expected `\"left\"`, `\"right\"` or `\"center\"`"
+        );
+        assert_eq!(
+            error(parse_quote! { struct S { #[table(format = "{}", with = f)] a: u8 } }),
+            "`format` and `with` can't be used together"
+        );
+        assert_eq!(
+            error(parse_quote! { struct S; }),
+            "no data to display for zero-sized types"
+        );
+        assert_eq!(
+            error(parse_quote! { union U { a: u8 } }),
+            "can only derive this trait on structs and enums"
+        );
+    }
+
+    #[test]
+    fn enum_errors() {
+        assert_eq!(
+            error(parse_quote! { #[table(per_variant)] struct S { a: u8 } }),
+            "`per_variant` can only be used on enums"
+        );
+        assert_eq!(
+            error(parse_quote! { enum E { #[table(skip)] A { a: u8 } } }),
+            "`skip` can't be used on variants"
+        );
+        assert_eq!(
+            error(parse_quote! { enum E { A(#[table(per_variant)] u8) } }),
+            "`per_variant` can only be used on structs and enums"
+        );
+        assert_eq!(
+            error(parse_quote! { enum E { A { #[table(bogus = 1)] a: u8 } } }),
+            "unknown attribute `bogus`"
+        );
+        assert_eq!(
+            error(parse_quote! { enum E {} }),
+            "no data to display for zero-sized types"
+        );
     }
 }
@@ -0,0 +1,195 @@
+use pretty_assertions::assert_eq;
+use term_data_table::{data_table, Alignment, IntoRow, Row, TableStyle};
+
+/// The text of each cell in a row.
+fn texts(row: &Row) -> Vec<String> {
+    row.cells()
+        .iter()
+        .map(|cell| cell.content().to_string())
+        .collect()
+}
+
+fn two_decimals(value: &f64) -> String {
+    format!("{value:.2}")
+}
+
+#[derive(IntoRow)]
+#[table(rename_all = "SCREAMING-KEBAB-CASE")]
+struct Item {
+    #[table(order = 1)]
+    unit_price: f64,
+    #[table(rename = "Name", order = 0)]
+    item_name: &'static str,
+    #[table(skip)]
+    #[allow(dead_code)]
+    internal_id: u64,
+    #[table(format = "{:>3}%")]
+    discount: u8,
+    #[table(with = two_decimals, align = "right")]
+    total: f64,
+    #[table(align = "center")]
+    r#type: char,
+}
+
+fn item() -> Item {
+    Item {
+        unit_price: 1.5,
+        item_name: "rope",
+        internal_id: 42,
+        discount: 5,
+        total: 3.0,
+        r#type: 'x',
+    }
+}
+
+#[test]
+fn field_attributes() {
+    let item = item();
+    assert_eq!(
+        texts(&item.headers()),
+        vec!["Name", "UNIT-PRICE", "DISCOUNT", "TOTAL", "TYPE"]
+    );
+    let row = item.into_row();
+    assert_eq!(texts(&row), vec!["rope", "1.5", "  5%", "3.00", "x"]);
+    let alignments: Vec<_> = row.cells().iter().map(|cell| cell.alignment()).collect();
+    assert_eq!(
+        alignments,
+        vec![
+            Alignment::Left,
+            Alignment::Left,
+            Alignment::Left,
+            Alignment::Right,
+            Alignment::Center,
+        ]
+    );
+}
+
+#[test]
+fn rendered() {
+    #[derive(IntoRow)]
+    #[table(rename_all = "Title Case")]
+    struct Tuple(&'static str, #[table(rename = "count")] u32);
+
+    let rows = [Tuple("a", 1), Tuple("b", 22)];
+    let table = data_table(&rows)
+        .with_header(rows[0].headers())
+        .with_style(TableStyle::SIMPLE)
+        .with_separate_rows(false);
+    let expected = "+---+-------+
+| 0 | count |
++---+-------+
+| a | 1     |
+| b | 22    |
++---+-------+
+";
+    assert_eq!(expected, table.to_string());
+}
+
+#[derive(IntoRow)]
+#[table(rename_all = "kebab-case")]
+enum Event {
+    Login {
+        user_name: &'static str,
+    },
+    #[table(rename = "logout")]
+    Logout(
+        #[table(rename = "user-name")] &'static str,
+        #[table(skip)]
+        #[allow(dead_code)]
+        u64,
+    ),
+    Purchase {
+        user_name: &'static str,
+        #[table(format = "${}", order = 0)]
+        amount: u32,
+    },
+    Shutdown,
+}
+
+fn events() -> [Event; 4] {
+    [
+        Event::Login { user_name: "ann" },
+        Event::Logout("ann", 7),
+        Event::Purchase {
+            user_name: "bob",
+            amount: 12,
+        },
+        Event::Shutdown,
+    ]
+}
+
+#[test]
+fn enum_union_columns() {
+    let events = events();
+    for event in &events {
+        assert_eq!(
+            texts(&event.headers()),
+            vec!["variant", "amount", "user-name"]
+        );
+    }
+    let rows: Vec<_> = events
+        .iter()
+        .map(|event| texts(&event.into_row()))
+        .collect();
+    assert_eq!(
+        rows,
+        vec![
+            vec!["Login", "", "ann"],
+            vec!["logout", "", "ann"],
+            vec!["Purchase", "$12", "bob"],
+            vec!["Shutdown", "", ""],
+        ]
+    );
+}
+
+#[test]
+fn enum_per_variant() {
+    #[derive(IntoRow)]
+    #[table(per_variant)]
+    enum Shape {
+        Circle { radius: u32 },
+        Rect(u32, #[table(rename = "height")] u32),
+        Point,
+    }
+
+    let shapes = [Shape::Circle { radius: 2 }, Shape::Rect(3, 4), Shape::Point];
+    let headers: Vec<_> = shapes.iter().map(|shape| texts(&shape.headers())).collect();
+    assert_eq!(
+        headers,
+        vec![
+            vec!["variant", "radius"],
+            vec!["variant", "0", "height"],
+            vec!["variant"],
+        ]
+    );
+    let rows: Vec<_> = shapes
+        .iter()
+        .map(|shape| texts(&shape.into_row()))
+        .collect();
+    assert_eq!(
+        rows,
+        vec![vec!["Circle", "2"], vec!["Rect", "3", "4"], vec!["Point"]]
+    );
+}
+
+/// The generated code doesn't depend on `format!` being the one from std.
+mod shadowed_format {
+    #[allow(unused_macros)]
+    macro_rules! format {
+        ($($tt:tt)*) => {
+            compile_error!("the derive used an unqualified `format!`")
+        };
+    }
+
+    #[derive(term_data_table::IntoRow)]
+    pub struct Price {
+        #[table(format = "${}")]
+        pub amount: u32,
+    }
+}
+
+#[test]
+fn qualified_format() {
+    let price = shadowed_format::Price { amount: 3 };
+    assert_eq!(texts(&price.into_row()), vec!["$3"]);
+}